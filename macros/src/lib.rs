@@ -0,0 +1,109 @@
+use eight_px_uint_eight::unix::*;
+use eight_px_uint_eight::*;
+use proc_macro::TokenStream;
+use quote::quote;
+use std::path::PathBuf;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, LitInt, LitStr, Token};
+
+struct Input {
+    path: LitStr,
+    layout: Ident,
+    threshold: Option<LitInt>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let layout = input.parse()?;
+
+        let threshold = match input.parse::<Option<Token![,]>>()? {
+            Some(_) if !input.is_empty() => Some(input.parse()?),
+            _ => None,
+        };
+
+        Ok(Self {
+            path,
+            layout,
+            threshold,
+        })
+    }
+}
+
+/// Embed an image file as a packed constant.
+///
+/// The path is relative to `CARGO_MANIFEST_DIR`, and PBM, XBM, BMP and PNG can be read.
+/// The layout is `Horizontal` or `Vertical`, and the threshold for PNG can follow.
+///
+/// ```ignore
+/// static LOGO: VerticalEightPxUintEight<ArrayEightData<1024>> =
+///     include_bitmap!("logo.pbm", Vertical);
+/// ```
+#[proc_macro]
+pub fn include_bitmap(input: TokenStream) -> TokenStream {
+    let Input {
+        path,
+        layout,
+        threshold,
+    } = parse_macro_input!(input as Input);
+
+    match expand(&path, &layout, threshold.as_ref()) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(
+    path: &LitStr,
+    layout: &Ident,
+    threshold: Option<&LitInt>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let threshold = match threshold {
+        Some(level) => Threshold::new(level.base10_parse()?),
+        None => Threshold::default(),
+    };
+
+    let full_path =
+        PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default()).join(path.value());
+    let full_path_str = full_path.to_string_lossy().to_string();
+
+    let src = std::fs::read(&full_path)
+        .map_err(|e| syn::Error::new(path.span(), format!("{}: {}", full_path_str, e)))?;
+    let image = load_image(&src, threshold)
+        .map_err(|e| syn::Error::new(path.span(), format!("{}: {}", full_path_str, e)))?;
+
+    let (width, height) = (image.width(), image.height());
+
+    let (bytes, ty) = match layout.to_string().as_str() {
+        "Horizontal" => (
+            image.as_vec().to_vec(),
+            quote!(::eight_px_uint_eight::HorizontalEightPxUintEight),
+        ),
+        "Vertical" => (
+            VerticalEightPxUintEight::from_image(&image)
+                .as_vec()
+                .to_vec(),
+            quote!(::eight_px_uint_eight::VerticalEightPxUintEight),
+        ),
+        _ => {
+            return Err(syn::Error::new(
+                layout.span(),
+                "layout must be Horizontal or Vertical",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        {
+            // rebuild when the image is changed
+            const _: &[u8] = include_bytes!(#full_path_str);
+
+            #ty::from_parts(
+                #width,
+                #height,
+                ::eight_px_uint_eight::ArrayEightData::new([#(#bytes),*]),
+            )
+        }
+    })
+}
@@ -0,0 +1,52 @@
+use eight_px_uint_eight::*;
+use eight_px_uint_eight_macros::include_bitmap;
+
+static HORIZONTAL: HorizontalEightPxUintEight<ArrayEightData<6>> =
+    include_bitmap!("tests/logo.pbm", Horizontal);
+
+static VERTICAL: VerticalEightPxUintEight<ArrayEightData<11>> =
+    include_bitmap!("tests/logo.pbm", Vertical);
+
+#[test]
+#[rustfmt::skip]
+fn test_horizontal() {
+    assert_eq!((11, 3), (HORIZONTAL.width(), HORIZONTAL.height()));
+    assert_eq!(
+        [
+            0b_1100_0010, 0b_0100_0000,
+            0b_1000_1001, 0b_1010_0000,
+            0b_0000_0000, 0b_0010_0000,
+        ],
+        HORIZONTAL.as_vec()
+    );
+}
+
+#[test]
+#[rustfmt::skip]
+fn test_vertical() {
+    assert_eq!((11, 3), (VERTICAL.width(), VERTICAL.height()));
+    assert_eq!(
+        [
+            0b_0000_0011,
+            0b_0000_0001,
+            0b_0000_0000,
+            0b_0000_0000,
+            0b_0000_0010,
+            0b_0000_0000,
+            0b_0000_0001,
+            0b_0000_0010,
+            0b_0000_0010,
+            0b_0000_0001,
+            0b_0000_0110,
+        ],
+        VERTICAL.as_vec()
+    );
+}
+
+#[test]
+fn test_local() {
+    let mut image = include_bitmap!("tests/logo.pbm", Horizontal, 128);
+    image.draw(0, 2, Mono::One);
+
+    assert_eq!(Mono::One, image.pixel(0, 2));
+}
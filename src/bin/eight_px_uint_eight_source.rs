@@ -0,0 +1,118 @@
+use eight_px_uint_eight::unix::*;
+use eight_px_uint_eight::*;
+use std::process::exit;
+
+const USAGE: &str = "Usage: eight_px_uint_eight_source <image> [--layout horizontal|vertical] [--language rust|c] [--name NAME] [--threshold 0-255]
+
+Read PBM, XBM, BMP or PNG and print it as a Rust or C source array.";
+
+struct Options {
+    path: String,
+    layout: Layout,
+    language: SourceLanguage,
+    name: Option<String>,
+    threshold: Threshold,
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut path = None;
+    let mut layout = Layout::Horizontal;
+    let mut language = SourceLanguage::Rust;
+    let mut name = None;
+    let mut threshold = Threshold::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", arg));
+
+        match arg.as_str() {
+            "--layout" => {
+                layout = match value()?.as_str() {
+                    "horizontal" => Layout::Horizontal,
+                    "vertical" => Layout::Vertical,
+                    other => return Err(format!("unknown layout: {}", other)),
+                }
+            }
+            "--language" => {
+                language = match value()?.as_str() {
+                    "rust" => SourceLanguage::Rust,
+                    "c" => SourceLanguage::C,
+                    other => return Err(format!("unknown language: {}", other)),
+                }
+            }
+            "--name" => name = Some(value()?.clone()),
+            "--threshold" => {
+                let level = value()?;
+                threshold = Threshold::new(
+                    level
+                        .parse()
+                        .map_err(|_| format!("invalid threshold: {}", level))?,
+                )
+            }
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            other if path.is_none() => path = Some(other.to_string()),
+            other => return Err(format!("unexpected argument: {}", other)),
+        }
+    }
+
+    Ok(Options {
+        path: path.ok_or_else(|| USAGE.to_string())?,
+        layout,
+        language,
+        name,
+        threshold,
+    })
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let Options {
+        path,
+        layout,
+        language,
+        name,
+        threshold,
+    } = match parse_options(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            exit(2);
+        }
+    };
+
+    let src = match std::fs::read(&path) {
+        Ok(src) => src,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            exit(1);
+        }
+    };
+
+    let image = match load_image(&src, threshold) {
+        Ok(image) => image,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            exit(1);
+        }
+    };
+
+    // file name without extension
+    let name = name.unwrap_or_else(|| {
+        std::path::Path::new(&path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "image".to_string())
+    });
+
+    let source = match layout {
+        Layout::Horizontal => to_source(&image, &name, language),
+        Layout::Vertical => to_source(
+            &VerticalEightPxUintEight::from_image(&image),
+            &name,
+            language,
+        ),
+    };
+
+    print!("{}", source);
+}
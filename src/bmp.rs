@@ -0,0 +1,243 @@
+use crate::*;
+use core::cmp::min;
+
+const FILE_HEADER_LENGTH: usize = 14;
+const INFO_HEADER_LENGTH: usize = 40;
+#[cfg(feature = "std")]
+const PALETTE_LENGTH: usize = 8;
+
+/// Monochrome Windows BMP.
+///
+/// The darker color of the palette is treated as `Mono::One`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Bmp<'a> {
+    width: usize,
+    height: usize,
+    top_down: bool,
+    inverted: bool,
+    stride: usize,
+    pixels: &'a [u8],
+}
+
+/// Rows are padded to 4 bytes.
+fn compute_stride(width: usize) -> usize {
+    width.div_ceil(32) * 4
+}
+
+fn u16_at(src: &[u8], i: usize) -> EightPxUintEightResult<u16> {
+    match src.get(i..i + 2) {
+        Some(b) => Ok(u16::from_le_bytes([b[0], b[1]])),
+        None => Err(EightPxUintEightError::InvalidFormat),
+    }
+}
+
+fn u32_at(src: &[u8], i: usize) -> EightPxUintEightResult<u32> {
+    match src.get(i..i + 4) {
+        Some(b) => Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        None => Err(EightPxUintEightError::InvalidFormat),
+    }
+}
+
+impl<'a> Bmp<'a> {
+    pub fn parse(src: &'a [u8]) -> EightPxUintEightResult<Self> {
+        if src.get(0..2) != Some(b"BM") {
+            return Err(EightPxUintEightError::InvalidFormat);
+        }
+
+        let offset = u32_at(src, 10)? as usize;
+        let info_length = u32_at(src, 14)? as usize;
+        let width = u32_at(src, 18)? as i32;
+        let height = u32_at(src, 22)? as i32;
+        let bit_count = u16_at(src, 28)?;
+        let compression = u32_at(src, 30)?;
+
+        // only BITMAPINFOHEADER and its successors without compression
+        if info_length < INFO_HEADER_LENGTH || bit_count != 1 || compression != 0 || width < 0 {
+            return Err(EightPxUintEightError::InvalidFormat);
+        }
+
+        // two BGRX colors after the info header
+        let palette = match FILE_HEADER_LENGTH
+            .checked_add(info_length)
+            .and_then(|start| src.get(start..)?.get(..8))
+        {
+            Some(palette) => palette,
+            None => return Err(EightPxUintEightError::InvalidFormat),
+        };
+        let zero = palette[0..3].iter().map(|c| *c as u16).sum::<u16>();
+        let one = palette[4..7].iter().map(|c| *c as u16).sum::<u16>();
+
+        let width = width as usize;
+        let top_down = height < 0;
+        let height = height.unsigned_abs() as usize;
+        let stride = compute_stride(width);
+
+        let end = stride
+            .checked_mul(height)
+            .and_then(|length| length.checked_add(offset))
+            .ok_or(EightPxUintEightError::InvalidFormat)?;
+        let pixels = match src.get(offset..end) {
+            Some(pixels) => pixels,
+            None => return Err(EightPxUintEightError::InvalidLengthData),
+        };
+
+        Ok(Self {
+            width,
+            height,
+            top_down,
+            inverted: zero < one,
+            stride,
+            pixels,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Mono {
+        let row = match self.top_down {
+            true => y,
+            false => self.height - 1 - y,
+        };
+        let byte = self.pixels[self.stride * row + (x >> 3)];
+
+        match (byte & (0b_1000_0000 >> (x % 8)) != 0) != self.inverted {
+            true => Mono::One,
+            false => Mono::Zero,
+        }
+    }
+
+    /// Draw pixels from (0, 0). Pixels that overflow are discarded.
+    pub fn write_into(&self, image: &mut impl EightPxUintEight) {
+        for y in 0..min(self.height, image.height()) {
+            for x in 0..min(self.width, image.width()) {
+                image.draw(x, y, self.pixel(x, y));
+            }
+        }
+    }
+}
+
+/// Encode the image into a bottom-up BMP that has white and black palette.
+#[cfg(feature = "std")]
+pub fn to_bmp(image: &impl EightPxUintEight) -> Vec<u8> {
+    let (width, height) = (image.width(), image.height());
+    let stride = compute_stride(width);
+    let offset = FILE_HEADER_LENGTH + INFO_HEADER_LENGTH + PALETTE_LENGTH;
+    let size = offset + stride * height;
+
+    let mut bmp = Vec::with_capacity(size);
+
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&(size as u32).to_le_bytes());
+    bmp.extend_from_slice(&[0; 4]);
+    bmp.extend_from_slice(&(offset as u32).to_le_bytes());
+
+    bmp.extend_from_slice(&(INFO_HEADER_LENGTH as u32).to_le_bytes());
+    bmp.extend_from_slice(&(width as i32).to_le_bytes());
+    bmp.extend_from_slice(&(height as i32).to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&((stride * height) as u32).to_le_bytes());
+    // 72 dpi
+    bmp.extend_from_slice(&2835u32.to_le_bytes());
+    bmp.extend_from_slice(&2835u32.to_le_bytes());
+    bmp.extend_from_slice(&2u32.to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+
+    bmp.extend_from_slice(&[0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+    for y in (0..height).rev() {
+        let mut row = vec![0; stride];
+
+        for x in 0..width {
+            if image.pixel(x, y) == Mono::One {
+                row[x >> 3] |= 0b_1000_0000 >> (x % 8);
+            }
+        }
+
+        bmp.extend_from_slice(&row);
+    }
+
+    bmp
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::{sample, EightDataClient};
+
+    #[test]
+    fn test_to_bmp() {
+        let bmp = to_bmp(&sample());
+
+        assert_eq!(62 + 8, bmp.len());
+        assert_eq!(
+            [
+                0b_1000_1001, 0b_1010_0000, 0, 0,
+                0b_1100_0010, 0b_0100_0000, 0, 0,
+            ],
+            bmp[62..]
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let src = to_bmp(&sample());
+        let bmp = Bmp::parse(&src).unwrap();
+        assert_eq!((11, 2), (bmp.width(), bmp.height()));
+
+        let mut vertical = VerticalEightPxUintEight::new(11, 2, EightDataClient::new(11)).unwrap();
+        bmp.write_into(&mut vertical);
+
+        let mut horizontal = HorizontalEightPxUintEight::new(11, 2, EightDataClient::new(4)).unwrap();
+        horizontal.blit(0, 0, &vertical, (0, 0, 11, 2));
+
+        assert_eq!(sample().as_vec(), horizontal.as_vec());
+    }
+
+    #[test]
+    fn test_parse_inverted_top_down() {
+        let mut src = to_bmp(&sample());
+
+        // black, white
+        src[54..62].copy_from_slice(&[0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0x00]);
+        // top-down
+        src[22..26].copy_from_slice(&(-2i32).to_le_bytes());
+
+        let bmp = Bmp::parse(&src).unwrap();
+        assert_eq!(Mono::Zero, bmp.pixel(0, 0));
+        assert_eq!(Mono::One, bmp.pixel(1, 0));
+        assert_eq!(Mono::Zero, bmp.pixel(1, 1));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut src = to_bmp(&sample());
+        src[28] = 8;
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), Bmp::parse(&src));
+
+        let src = to_bmp(&sample());
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), Bmp::parse(&src[..66]));
+
+        let mut src = to_bmp(&sample());
+        src[14..18].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), Bmp::parse(&src));
+
+        let mut src = to_bmp(&sample());
+        src[10..14].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(Bmp::parse(&src).is_err());
+
+        // 4 bytes per row of 0x7fff_ffff rows overflow 32 bits
+        let mut src = to_bmp(&sample());
+        src[22..26].copy_from_slice(&i32::MAX.to_le_bytes());
+        assert!(Bmp::parse(&src).is_err());
+    }
+}
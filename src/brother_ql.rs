@@ -0,0 +1,262 @@
+use crate::*;
+
+const ESC: u8 = 0x1b;
+const INVALIDATE_LENGTH: usize = 200;
+const MAX_LINE_LENGTH: usize = 162;
+
+/// Commands before raster lines except the compression.
+const SETUP_LENGTH: usize = INVALIDATE_LENGTH + 2 + 4 + 13 + 4 + 4 + 4 + 5;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum QlMedia {
+    /// Endless tape. The length of the label is the height of the image.
+    Continuous,
+    DieCut,
+}
+
+/// Raster protocol of Brother QL label printers.
+///
+/// The image must be rotated so that its rows go across the tape, and its width is the printable width of the media.
+/// (ex: 696 dots for 62mm tape) Rows are mirrored and placed from `offset` dots of the line
+/// because the print head has its first pin at the right. `Mono::One` is black.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct BrotherQl {
+    media: QlMedia,
+    width_mm: u8,
+    length_mm: u8,
+    line_length: usize,
+    offset: usize,
+    margin: u16,
+    compression: bool,
+    auto_cut: bool,
+}
+
+impl BrotherQl {
+    /// `length_mm` is 0 for continuous tape. Lines are 90 bytes (720 pins) of QL-500 to QL-820.
+    pub fn new(media: QlMedia, width_mm: u8, length_mm: u8) -> Self {
+        Self {
+            media,
+            width_mm,
+            length_mm,
+            line_length: 90,
+            offset: 0,
+            margin: match media {
+                QlMedia::Continuous => 35,
+                QlMedia::DieCut => 0,
+            },
+            compression: true,
+            auto_cut: true,
+        }
+    }
+
+    /// 162 bytes for wide printers. ex: QL-1100
+    pub fn with_line_length(self, line_length: usize) -> Self {
+        Self {
+            line_length: line_length.min(MAX_LINE_LENGTH),
+            ..self
+        }
+    }
+
+    /// Dots from the right edge of the head to the media. ex: 12 for 62mm tape
+    pub fn with_offset(self, offset: usize) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Feed margin in dots.
+    pub fn with_margin(self, margin: u16) -> Self {
+        Self { margin, ..self }
+    }
+
+    /// PackBits for raster lines.
+    pub fn with_compression(self, compression: bool) -> Self {
+        Self {
+            compression,
+            ..self
+        }
+    }
+
+    pub fn with_auto_cut(self, auto_cut: bool) -> Self {
+        Self { auto_cut, ..self }
+    }
+
+    /// Length enough for an image of the height.
+    pub fn max_encoded_length(&self, height: usize) -> usize {
+        let line = match self.compression {
+            true => 3 + pack_bits_max_length(self.line_length),
+            false => 3 + self.line_length,
+        };
+        SETUP_LENGTH + 2 + line * height + 1
+    }
+
+    fn media_type(&self) -> u8 {
+        match self.media {
+            QlMedia::Continuous => 0x0a,
+            QlMedia::DieCut => 0x0b,
+        }
+    }
+
+    /// Encode the image as a label. Return the length written.
+    pub fn encode<D: EightData>(
+        &self,
+        image: &HorizontalEightPxUintEight<D>,
+        dst: &mut [u8],
+    ) -> EightPxUintEightResult<usize> {
+        let pins = self.line_length * 8;
+        if self.offset + image.width() > pins {
+            return Err(EightPxUintEightError::Overflow((
+                pins,
+                self.offset + image.width(),
+            )));
+        }
+
+        let mut output = Output::new(dst);
+        let lines = (image.height() as u32).to_le_bytes();
+        let [margin_l, margin_h] = self.margin.to_le_bytes();
+
+        output.put(&[0; INVALIDATE_LENGTH])?;
+        output.put(&[ESC, b'@'])?;
+        // raster mode
+        output.put(&[ESC, b'i', b'a', 0x01])?;
+        // media: valid kind, width and length, and recovery on
+        let (kind, width, length) = (self.media_type(), self.width_mm, self.length_mm);
+        output.put(&[ESC, b'i', b'z', 0x8e, kind, width, length])?;
+        output.put(&[lines[0], lines[1], lines[2], lines[3], 0, 0])?;
+        output.put(&[ESC, b'i', b'M', if self.auto_cut { 0x40 } else { 0 }])?;
+        // cut every label
+        output.put(&[ESC, b'i', b'A', 0x01])?;
+        // cut at end
+        output.put(&[ESC, b'i', b'K', 0x08])?;
+        output.put(&[ESC, b'i', b'd', margin_l, margin_h])?;
+        output.put(&[b'M', if self.compression { 0x02 } else { 0x00 }])?;
+
+        let mut line = [0; MAX_LINE_LENGTH];
+        let line = &mut line[..self.line_length];
+        let mut packed = [0; pack_bits_max_length(MAX_LINE_LENGTH)];
+
+        for y in 0..image.height() {
+            line.iter_mut().for_each(|byte| *byte = 0);
+            for x in 0..image.width() {
+                if image.pixel(x, y) == Mono::One {
+                    let pin = self.offset + image.width() - 1 - x;
+                    line[pin >> 3] |= 0b_1000_0000 >> (pin % 8);
+                }
+            }
+
+            match self.compression {
+                true if line.iter().all(|byte| *byte == 0) => {
+                    output.put(b"Z")?;
+                }
+                true => {
+                    let length = pack_bits(line, &mut packed)?;
+                    output.put(&[b'g', 0x00, length as u8])?;
+                    output.put(&packed[..length])?;
+                }
+                false => {
+                    output.put(&[b'g', 0x00, self.line_length as u8])?;
+                    output.put(line)?;
+                }
+            }
+        }
+
+        // print the last page
+        output.put(&[0x1a])
+    }
+
+    #[cfg(feature = "std")]
+    pub fn to_vec<D: EightData>(
+        &self,
+        image: &HorizontalEightPxUintEight<D>,
+    ) -> EightPxUintEightResult<Vec<u8>> {
+        let mut dst = vec![0; self.max_encoded_length(image.height())];
+        let length = self.encode(image, &mut dst)?;
+        dst.truncate(length);
+
+        Ok(dst)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+
+    #[test]
+    fn test_setup() {
+        let mut image = HorizontalEightPxUintEight::new(12, 3, EightDataClient::new(6)).unwrap();
+        image.fill((0, 0, 4, 1), Mono::One);
+        image.fill((11, 2, 1, 1), Mono::One);
+
+        let vec = BrotherQl::new(QlMedia::DieCut, 29, 90).to_vec(&image).unwrap();
+
+        assert!(vec[..200].iter().all(|byte| *byte == 0));
+        assert_eq!(
+            vec![
+                0x1b, b'@',
+                0x1b, b'i', b'a', 0x01,
+                0x1b, b'i', b'z', 0x8e, 0x0b, 29, 90, 3, 0, 0, 0, 0, 0,
+                0x1b, b'i', b'M', 0x40,
+                0x1b, b'i', b'A', 0x01,
+                0x1b, b'i', b'K', 0x08,
+                0x1b, b'i', b'd', 0, 0,
+                b'M', 0x02,
+            ],
+            vec[200..238]
+        );
+        assert_eq!(Some(&0x1a), vec.last());
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut image = HorizontalEightPxUintEight::new(12, 3, EightDataClient::new(6)).unwrap();
+        image.fill((0, 0, 4, 1), Mono::One);
+        image.fill((11, 2, 1, 1), Mono::One);
+
+        let printer = BrotherQl::new(QlMedia::Continuous, 62, 0).with_offset(12);
+        let vec = printer.to_vec(&image).unwrap();
+
+        // the row is mirrored into pins 12..24, so x = 0 is pin 23
+        assert_eq!(
+            vec![
+                b'g', 0x00, 6, 0xff, 0x00, 0x00, 0b_0000_1111, 0xaa, 0x00,
+                b'Z',
+                b'g', 0x00, 5, 0x01, 0x00, 0b_0000_1000, 0xa9, 0x00,
+                0x1a,
+            ],
+            vec[238..]
+        );
+
+        let vec = printer.with_compression(false).to_vec(&image).unwrap();
+        assert_eq!(238 + 3 * 93 + 1, vec.len());
+        assert_eq!(&[b'g', 0x00, 90, 0x00, 0x00, 0b_0000_1111], &vec[238..244]);
+    }
+
+    #[test]
+    fn test_incompressible_lines() {
+        for line_length in [90, 162] {
+            let width = line_length * 8;
+            let mut image = HorizontalEightPxUintEight::new(width, 2, EightDataClient::new(line_length * 2)).unwrap();
+            for x in (0..width).step_by(24) {
+                image.fill((x, 0, 8, 2), Mono::One);
+            }
+
+            let printer = BrotherQl::new(QlMedia::Continuous, 62, 0).with_line_length(line_length);
+            let vec = printer.to_vec(&image).unwrap();
+            assert!(vec.len() <= printer.max_encoded_length(2));
+            assert_eq!(Some(&0x1a), vec.last());
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let image = HorizontalEightPxUintEight::new(720, 1, EightDataClient::new(90)).unwrap();
+        let printer = BrotherQl::new(QlMedia::Continuous, 62, 0);
+
+        assert!(printer.to_vec(&image).is_ok());
+        assert_eq!(
+            Err(EightPxUintEightError::Overflow((720, 721))),
+            printer.with_offset(1).to_vec(&image)
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use crate::*;
+use core::cmp::min;
+
+/// Where the source image sits on a resized canvas.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Return offset of the source on the canvas. Negative when the canvas is smaller.
+    pub fn offset(&self, src: (usize, usize), canvas: (usize, usize)) -> (isize, isize) {
+        let (src_width, src_height) = src;
+        let (canvas_width, canvas_height) = canvas;
+
+        let rest_x = canvas_width as isize - src_width as isize;
+        let rest_y = canvas_height as isize - src_height as isize;
+
+        let x = match self {
+            Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft => 0,
+            Anchor::Top | Anchor::Center | Anchor::Bottom => rest_x / 2,
+            Anchor::TopRight | Anchor::Right | Anchor::BottomRight => rest_x,
+        };
+        let y = match self {
+            Anchor::TopLeft | Anchor::Top | Anchor::TopRight => 0,
+            Anchor::Left | Anchor::Center | Anchor::Right => rest_y / 2,
+            Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight => rest_y,
+        };
+
+        (x, y)
+    }
+}
+
+/// Return the part of the rectangle that is inside of width * height.
+///
+/// A rectangle without pixels inside is always 0 * 0 at the origin.
+pub fn clip(xywh: impl ActAsXywh, width: usize, height: usize) -> Rectangle {
+    let (x, y, w, h) = xywh.xywh();
+
+    if x >= width || y >= height {
+        return Rectangle::new(0, 0, 0, 0);
+    }
+
+    match (min(w, width - x), min(h, height - y)) {
+        (0, _) | (_, 0) => Rectangle::new(0, 0, 0, 0),
+        (w, h) => Rectangle::new(x, y, w, h),
+    }
+}
+
+/// Place `src` on `canvas` at the anchor and paint the rest with `fill`.
+pub fn resize_into(
+    src: &impl EightPxUintEight,
+    canvas: &mut impl EightPxUintEight,
+    anchor: Anchor,
+    fill: Mono,
+) {
+    let (offset_x, offset_y) = anchor.offset(
+        (src.width(), src.height()),
+        (canvas.width(), canvas.height()),
+    );
+
+    for y in 0..canvas.height() {
+        for x in 0..canvas.width() {
+            let src_x = x as isize - offset_x;
+            let src_y = y as isize - offset_y;
+
+            let color = if src_x < 0
+                || src_y < 0
+                || src_x as usize >= src.width()
+                || src_y as usize >= src.height()
+            {
+                fill
+            } else {
+                src.pixel(src_x as usize, src_y as usize)
+            };
+
+            canvas.draw(x, y, color);
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::{horizontal, EightDataClient};
+
+    const PIXELS: [u8; 12] = [
+        1, 0, 0, 1,
+        0, 1, 1, 0,
+        1, 1, 1, 1,
+    ];
+
+    #[test]
+    fn test_crop() {
+        let image = horizontal(4, 3, &PIXELS);
+
+        let cropped = image.crop((1, 1, 3, 2), EightDataClient::new(2)).unwrap();
+        assert_eq!((3, 2), (cropped.width(), cropped.height()));
+        assert_eq!(
+            [
+                0b_1100_0000,
+                0b_1110_0000,
+            ],
+            cropped.as_vec()
+        );
+
+        // overflowing rectangle is clipped
+        let cropped = image.crop((2, 1, 9, 9), EightDataClient::new(2)).unwrap();
+        assert_eq!((2, 2), (cropped.width(), cropped.height()));
+
+        assert!(image.crop((0, 0, 4, 3), EightDataClient::new(2)).is_err());
+
+        assert_eq!(2, image.crop_length((2, 1, 9, 9)));
+        assert_eq!(3, image.crop_length((0, 0, 4, 3)));
+    }
+
+    #[test]
+    fn test_crop_outside() {
+        let image = horizontal(4, 3, &PIXELS);
+
+        for xywh in [(4, 0, 4, 2), (1, 3, 2, 2), (1, 1, 0, 2), (9, 9, 1, 1)] {
+            assert_eq!(Rectangle::new(0, 0, 0, 0), clip(xywh, 4, 3));
+            assert_eq!(0, image.crop_length(xywh));
+
+            let cropped = image.crop(xywh, EightDataClient::new(0)).unwrap();
+            assert_eq!((0, 0), (cropped.width(), cropped.height()));
+        }
+
+        let image = VerticalEightPxUintEight::new(8, 8, EightDataClient::new(8)).unwrap();
+        assert_eq!(0, image.crop_length((8, 0, 4, 2)));
+        assert!(image.crop((8, 0, 4, 2), EightDataClient::new(0)).is_ok());
+    }
+
+    #[test]
+    fn test_blit() {
+        let image = horizontal(4, 3, &PIXELS);
+
+        let mut vertical = VerticalEightPxUintEight::new(3, 3, EightDataClient::new(3)).unwrap();
+        vertical.blit(1, 0, &image, (0, 0, 4, 3));
+        assert_eq!([0b_0000_0000, 0b_0000_0101, 0b_0000_0110], vertical.as_vec());
+    }
+
+    #[test]
+    fn test_resize() {
+        let image = horizontal(4, 3, &PIXELS);
+
+        let resized = image.resize(6, 5, Anchor::Center, Mono::Zero, EightDataClient::new(5)).unwrap();
+        assert_eq!(
+            [
+                0b_0000_0000,
+                0b_0100_1000,
+                0b_0011_0000,
+                0b_0111_1000,
+                0b_0000_0000,
+            ],
+            resized.as_vec()
+        );
+
+        let resized = image.resize(2, 2, Anchor::BottomRight, Mono::One, EightDataClient::new(2)).unwrap();
+        assert_eq!(
+            [
+                0b_1000_0000,
+                0b_1100_0000,
+            ],
+            resized.as_vec()
+        );
+
+        let resized = image.resize(5, 3, Anchor::TopLeft, Mono::One, EightDataClient::new(3)).unwrap();
+        assert_eq!(
+            [
+                0b_1001_1000,
+                0b_0110_1000,
+                0b_1111_1000,
+            ],
+            resized.as_vec()
+        );
+    }
+
+    #[test]
+    fn test_anchor_offset() {
+        assert_eq!((0, 0), Anchor::TopLeft.offset((4, 4), (8, 6)));
+        assert_eq!((2, 1), Anchor::Center.offset((4, 4), (8, 6)));
+        assert_eq!((4, 2), Anchor::BottomRight.offset((4, 4), (8, 6)));
+        assert_eq!((-2, -1), Anchor::Center.offset((8, 6), (4, 4)));
+    }
+}
@@ -1,4 +1,5 @@
-pub fn compute_eight_length(src: usize) -> usize {
+#[allow(clippy::redundant_guards)]
+pub const fn compute_eight_length(src: usize) -> usize {
     match src >> 3 {
         m if m == 0 => 1,
         m if src % 8 == 0 => m,
@@ -20,11 +21,22 @@ pub fn into_as_eight(src: usize, length: usize) -> AsEight {
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Mono {
     Zero,
     One,
 }
 
+/// How pixels are packed into bytes.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Layout {
+    /// 8 pixels in a row. Lower x populates higher bit. `0123_4567`
+    Horizontal,
+    /// 8 pixels in a column. Lower y populates lower bit. `7654_3210`
+    Vertical,
+}
+
 pub trait ActAsMono {
     fn act_as(&self) -> Mono;
 }
@@ -44,6 +56,57 @@ impl ActAsMono for u8 {
     }
 }
 
+impl ActAsMono for bool {
+    fn act_as(&self) -> Mono {
+        match self {
+            false => Mono::Zero,
+            true => Mono::One,
+        }
+    }
+}
+
+/// 8-bit luminance. 0 is black.
+pub trait ActAsLuma {
+    fn luma(&self) -> u8;
+}
+
+impl ActAsLuma for u8 {
+    fn luma(&self) -> u8 {
+        *self
+    }
+}
+
+impl ActAsLuma for (u8, u8, u8) {
+    fn luma(&self) -> u8 {
+        let (r, g, b) = *self;
+        // ITU-R BT.601
+        ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+    }
+}
+
+impl ActAsLuma for [u8; 3] {
+    fn luma(&self) -> u8 {
+        (self[0], self[1], self[2]).luma()
+    }
+}
+
+/// Transparent pixels are composed on white.
+impl ActAsLuma for (u8, u8, u8, u8) {
+    fn luma(&self) -> u8 {
+        let (r, g, b, a) = *self;
+        let luma = (r, g, b).luma() as u32;
+        let alpha = a as u32;
+
+        ((luma * alpha + 255 * (255 - alpha)) / 255) as u8
+    }
+}
+
+impl ActAsLuma for [u8; 4] {
+    fn luma(&self) -> u8 {
+        (self[0], self[1], self[2], self[3]).luma()
+    }
+}
+
 pub trait ActAsXywh {
     fn xywh(&self) -> (usize, usize, usize, usize);
 }
@@ -55,6 +118,7 @@ impl ActAsXywh for (usize, usize, usize, usize) {
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rectangle {
     pub x: usize,
     pub y: usize,
@@ -86,6 +150,7 @@ impl Rectangle {
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Part {
     pub src_x: usize,
     pub src_y: usize,
@@ -115,6 +180,35 @@ impl Part {
     }
 }
 
+/// Bytes written into `dst` one after another. Overflow is an error instead of a panic.
+pub(crate) struct Output<'a> {
+    dst: &'a mut [u8],
+    written: usize,
+}
+
+impl<'a> Output<'a> {
+    pub(crate) fn new(dst: &'a mut [u8]) -> Self {
+        Self { dst, written: 0 }
+    }
+
+    pub(crate) fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Return the length written so far.
+    pub(crate) fn put(&mut self, bytes: &[u8]) -> crate::EightPxUintEightResult<usize> {
+        let end = self.written + bytes.len();
+        match self.dst.get_mut(self.written..end) {
+            Some(d) => {
+                d.copy_from_slice(bytes);
+                self.written = end;
+                Ok(end)
+            }
+            None => Err(crate::EightPxUintEightError::Overflow((self.dst.len(), end))),
+        }
+    }
+}
+
 mod test {
     #[allow(unused_imports)]
     use crate::compute_eight_length;
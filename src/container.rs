@@ -0,0 +1,524 @@
+use crate::*;
+use core::cmp::min;
+
+const MAGIC: &[u8; 4] = b"E8PX";
+
+pub const CONTAINER_VERSION: u8 = 1;
+
+/// magic(4), version, layout, bit order, compression, width(u16 LE), height(u16 LE), payload length(u32 LE)
+pub const CONTAINER_HEADER_LENGTH: usize = 16;
+
+/// Which bit of a byte has the lower coordinate.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum BitOrder {
+    /// Lower coordinate populates higher bit. `HorizontalEightPxUintEight` is so.
+    MsbFirst,
+    /// Lower coordinate populates lower bit. `VerticalEightPxUintEight` is so.
+    LsbFirst,
+}
+
+impl BitOrder {
+    pub fn of(layout: Layout) -> Self {
+        match layout {
+            Layout::Horizontal => BitOrder::MsbFirst,
+            Layout::Vertical => BitOrder::LsbFirst,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Compression {
+    Raw,
+    PackBits,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct ContainerOptions {
+    /// Bit order of the payload. The order of the layout with `None`.
+    pub bit_order: Option<BitOrder>,
+    pub compression: Compression,
+}
+
+impl Default for ContainerOptions {
+    fn default() -> Self {
+        Self {
+            bit_order: None,
+            compression: Compression::Raw,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct ContainerHeader {
+    pub width: usize,
+    pub height: usize,
+    pub layout: Layout,
+    pub bit_order: BitOrder,
+    pub compression: Compression,
+    /// Length of the payload that follows the header.
+    pub payload_length: usize,
+}
+
+impl ContainerHeader {
+    pub fn parse(src: &[u8]) -> EightPxUintEightResult<Self> {
+        let header = src
+            .get(..CONTAINER_HEADER_LENGTH)
+            .ok_or(EightPxUintEightError::InvalidFormat)?;
+
+        if &header[0..4] != MAGIC || header[4] != CONTAINER_VERSION {
+            return Err(EightPxUintEightError::InvalidFormat);
+        }
+
+        let layout = match header[5] {
+            0 => Layout::Horizontal,
+            1 => Layout::Vertical,
+            _ => return Err(EightPxUintEightError::InvalidFormat),
+        };
+        let bit_order = match header[6] {
+            0 => BitOrder::MsbFirst,
+            1 => BitOrder::LsbFirst,
+            _ => return Err(EightPxUintEightError::InvalidFormat),
+        };
+        let compression = match header[7] {
+            0 => Compression::Raw,
+            1 => Compression::PackBits,
+            _ => return Err(EightPxUintEightError::InvalidFormat),
+        };
+
+        let header = Self {
+            width: u16::from_le_bytes([header[8], header[9]]) as usize,
+            height: u16::from_le_bytes([header[10], header[11]]) as usize,
+            layout,
+            bit_order,
+            compression,
+            payload_length: u32::from_le_bytes([header[12], header[13], header[14], header[15]])
+                as usize,
+        };
+
+        let end = CONTAINER_HEADER_LENGTH
+            .checked_add(header.payload_length)
+            .ok_or(EightPxUintEightError::InvalidFormat)?;
+        if src.len() < end {
+            return Err(EightPxUintEightError::InvalidLengthData);
+        }
+
+        Ok(header)
+    }
+
+    /// Length of the unpacked data.
+    pub fn data_length(&self) -> usize {
+        let length = match self.layout {
+            Layout::Horizontal => {
+                HorizontalEightPxUintEight::<&mut [u8]>::data_length(self.width, self.height)
+            }
+            Layout::Vertical => {
+                VerticalEightPxUintEight::<&mut [u8]>::data_length(self.width, self.height)
+            }
+        };
+        length.expect("sizes are 16 bits")
+    }
+
+    fn write(&self, dst: &mut [u8]) {
+        let width = (self.width as u16).to_le_bytes();
+        let height = (self.height as u16).to_le_bytes();
+        let payload_length = (self.payload_length as u32).to_le_bytes();
+
+        dst[0..4].copy_from_slice(MAGIC);
+        dst[4] = CONTAINER_VERSION;
+        dst[5] = match self.layout {
+            Layout::Horizontal => 0,
+            Layout::Vertical => 1,
+        };
+        dst[6] = match self.bit_order {
+            BitOrder::MsbFirst => 0,
+            BitOrder::LsbFirst => 1,
+        };
+        dst[7] = match self.compression {
+            Compression::Raw => 0,
+            Compression::PackBits => 1,
+        };
+        dst[8..10].copy_from_slice(&width);
+        dst[10..12].copy_from_slice(&height);
+        dst[12..16].copy_from_slice(&payload_length);
+    }
+
+    /// Make an image on the unpacked data in the buffer. The bits are reversed in place if the bit order is not of the layout.
+    fn image<'b>(
+        &self,
+        data: &'b mut [u8],
+    ) -> EightPxUintEightResult<ContainerImage<&'b mut [u8]>> {
+        if self.bit_order != BitOrder::of(self.layout) {
+            data.iter_mut().for_each(|byte| *byte = byte.reverse_bits());
+        }
+
+        match self.layout {
+            Layout::Horizontal => Ok(ContainerImage::Horizontal(HorizontalEightPxUintEight::new(
+                self.width,
+                self.height,
+                data,
+            )?)),
+            Layout::Vertical => Ok(ContainerImage::Vertical(VerticalEightPxUintEight::new(
+                self.width,
+                self.height,
+                data,
+            )?)),
+        }
+    }
+}
+
+/// An image read from a container. Its layout is decided by the header.
+#[derive(Debug, Eq, PartialEq)]
+pub enum ContainerImage<D: EightData> {
+    Horizontal(HorizontalEightPxUintEight<D>),
+    Vertical(VerticalEightPxUintEight<D>),
+}
+
+impl<D: EightData> EightPxUintEight for ContainerImage<D> {
+    type EightData = D;
+
+    fn layout(&self) -> Layout {
+        match self {
+            ContainerImage::Horizontal(image) => image.layout(),
+            ContainerImage::Vertical(image) => image.layout(),
+        }
+    }
+
+    fn width(&self) -> usize {
+        match self {
+            ContainerImage::Horizontal(image) => image.width(),
+            ContainerImage::Vertical(image) => image.width(),
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self {
+            ContainerImage::Horizontal(image) => image.height(),
+            ContainerImage::Vertical(image) => image.height(),
+        }
+    }
+
+    fn eight_length(&self) -> usize {
+        match self {
+            ContainerImage::Horizontal(image) => image.eight_length(),
+            ContainerImage::Vertical(image) => image.eight_length(),
+        }
+    }
+
+    fn eight_data(&self) -> &D {
+        match self {
+            ContainerImage::Horizontal(image) => image.eight_data(),
+            ContainerImage::Vertical(image) => image.eight_data(),
+        }
+    }
+
+    fn eight_data_mut(&mut self) -> &mut D {
+        match self {
+            ContainerImage::Horizontal(image) => image.eight_data_mut(),
+            ContainerImage::Vertical(image) => image.eight_data_mut(),
+        }
+    }
+
+    fn draw(&mut self, x: usize, y: usize, color: Mono) {
+        match self {
+            ContainerImage::Horizontal(image) => image.draw(x, y, color),
+            ContainerImage::Vertical(image) => image.draw(x, y, color),
+        }
+    }
+
+    fn pixel(&self, x: usize, y: usize) -> Mono {
+        match self {
+            ContainerImage::Horizontal(image) => image.pixel(x, y),
+            ContainerImage::Vertical(image) => image.pixel(x, y),
+        }
+    }
+
+    fn compute_part(&self, xywh: impl ActAsXywh) -> Part {
+        match self {
+            ContainerImage::Horizontal(image) => image.compute_part(xywh),
+            ContainerImage::Vertical(image) => image.compute_part(xywh),
+        }
+    }
+}
+
+/// A raw image in a container, read without copying. ex: an image stored in flash
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct ContainerView<'a> {
+    header: ContainerHeader,
+    data: &'a [u8],
+}
+
+impl<'a> ContainerView<'a> {
+    pub fn header(&self) -> ContainerHeader {
+        self.header
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.header.layout
+    }
+
+    pub fn width(&self) -> usize {
+        self.header.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.header.height
+    }
+
+    /// The payload. It is the same layout as the image of `layout`.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Mono {
+        let (index, bit) = match self.header.layout {
+            Layout::Horizontal => (
+                compute_eight_length(self.header.width) * y + (x >> 3),
+                0b_1000_0000 >> (x % 8),
+            ),
+            Layout::Vertical => (self.header.width * (y >> 3) + x, 0b_0000_0001 << (y % 8)),
+        };
+
+        match self.data[index] & bit {
+            0 => Mono::Zero,
+            _ => Mono::One,
+        }
+    }
+
+    /// Draw pixels from (0, 0). Pixels that overflow are discarded.
+    pub fn write_into(&self, image: &mut impl EightPxUintEight) {
+        let width = min(self.header.width, image.width());
+        let height = min(self.header.height, image.height());
+
+        for y in 0..height {
+            for x in 0..width {
+                image.draw(x, y, self.pixel(x, y));
+            }
+        }
+    }
+}
+
+/// Read the raw payload of `src` without copying. `src` is not changed.
+///
+/// Compressed payloads and payloads whose bit order is not of the layout need `decode_container`.
+pub fn parse_container(src: &[u8]) -> EightPxUintEightResult<ContainerView<'_>> {
+    let header = ContainerHeader::parse(src)?;
+
+    if header.compression != Compression::Raw || header.bit_order != BitOrder::of(header.layout) {
+        return Err(EightPxUintEightError::InvalidFormat);
+    }
+    if header.payload_length != header.data_length() {
+        return Err(EightPxUintEightError::InvalidLengthData);
+    }
+
+    Ok(ContainerView {
+        header,
+        data: &src[CONTAINER_HEADER_LENGTH..CONTAINER_HEADER_LENGTH + header.payload_length],
+    })
+}
+
+/// Make an image on `buffer` with the payload of `src` unpacked.
+pub fn decode_container<'b>(
+    src: &[u8],
+    buffer: &'b mut [u8],
+) -> EightPxUintEightResult<ContainerImage<&'b mut [u8]>> {
+    let header = ContainerHeader::parse(src)?;
+    let payload = &src[CONTAINER_HEADER_LENGTH..CONTAINER_HEADER_LENGTH + header.payload_length];
+
+    let length = header.data_length();
+    let buffer_length = buffer.len();
+    let data = buffer
+        .get_mut(..length)
+        .ok_or(EightPxUintEightError::Overflow((buffer_length, length)))?;
+
+    let written = match header.compression {
+        Compression::Raw => {
+            data.get_mut(..payload.len())
+                .ok_or(EightPxUintEightError::InvalidLengthData)?
+                .copy_from_slice(payload);
+            payload.len()
+        }
+        Compression::PackBits => unpack_bits(payload, data)?,
+    };
+
+    if written != length {
+        return Err(EightPxUintEightError::InvalidLengthData);
+    }
+
+    header.image(data)
+}
+
+/// Write the image into `dst` with a header. Return the length written.
+pub fn write_container(
+    image: &impl EightPxUintEight,
+    options: ContainerOptions,
+    dst: &mut [u8],
+) -> EightPxUintEightResult<usize> {
+    if image.width() > u16::MAX as usize || image.height() > u16::MAX as usize {
+        return Err(EightPxUintEightError::InvalidLengthData);
+    }
+
+    let bit_order = options
+        .bit_order
+        .unwrap_or_else(|| BitOrder::of(image.layout()));
+    let reverse = bit_order != BitOrder::of(image.layout());
+    let map = |byte: u8| match reverse {
+        true => byte.reverse_bits(),
+        false => byte,
+    };
+
+    let src = image.as_vec();
+    let required = match options.compression {
+        Compression::Raw => CONTAINER_HEADER_LENGTH + src.len(),
+        Compression::PackBits => CONTAINER_HEADER_LENGTH,
+    };
+    if dst.len() < required {
+        return Err(EightPxUintEightError::Overflow((dst.len(), required)));
+    }
+
+    let payload = &mut dst[CONTAINER_HEADER_LENGTH..];
+    let payload_length = match options.compression {
+        Compression::Raw => {
+            payload.iter_mut().zip(src).for_each(|(d, s)| *d = map(*s));
+            src.len()
+        }
+        Compression::PackBits => pack_bits_by(src, payload, map)?,
+    };
+
+    ContainerHeader {
+        width: image.width(),
+        height: image.height(),
+        layout: image.layout(),
+        bit_order,
+        compression: options.compression,
+        payload_length,
+    }
+    .write(dst);
+
+    Ok(CONTAINER_HEADER_LENGTH + payload_length)
+}
+
+#[cfg(feature = "std")]
+pub fn to_container(
+    image: &impl EightPxUintEight,
+    options: ContainerOptions,
+) -> EightPxUintEightResult<Vec<u8>> {
+    let mut dst = vec![0; CONTAINER_HEADER_LENGTH + pack_bits_max_length(image.as_vec().len())];
+    let length = write_container(image, options, &mut dst)?;
+    dst.truncate(length);
+
+    Ok(dst)
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::{vertical, EightDataClient};
+
+    const PIXELS: [u8; 6] = [
+        1, 1, 0,
+        1, 0, 1,
+    ];
+
+    #[test]
+    fn test_header() {
+        let image = vertical(3, 2, &PIXELS);
+        let container = to_container(&image, ContainerOptions::default()).unwrap();
+
+        assert_eq!(
+            &[
+                b'E', b'8', b'P', b'X', 1, 1, 1, 0, 3, 0, 2, 0, 3, 0, 0, 0,
+                0b_0000_0011, 0b_0000_0001, 0b_0000_0010,
+            ],
+            container.as_slice()
+        );
+        assert_eq!(
+            Ok(ContainerHeader {
+                width: 3,
+                height: 2,
+                layout: Layout::Vertical,
+                bit_order: BitOrder::LsbFirst,
+                compression: Compression::Raw,
+                payload_length: 3,
+            }),
+            ContainerHeader::parse(&container)
+        );
+    }
+
+    #[test]
+    fn test_parse_container() {
+        let image = vertical(3, 2, &PIXELS);
+        let container = to_container(&image, ContainerOptions::default()).unwrap();
+        let parsed = parse_container(&container).unwrap();
+
+        assert_eq!((Layout::Vertical, 3, 2), (parsed.layout(), parsed.width(), parsed.height()));
+        assert_eq!(image.as_vec(), parsed.data());
+        assert_eq!(Mono::One, parsed.pixel(2, 1));
+        assert_eq!(Mono::Zero, parsed.pixel(2, 0));
+
+        // the source is not changed, so it can be parsed again
+        assert_eq!(parsed, parse_container(&container).unwrap());
+
+        let mut written = HorizontalEightPxUintEight::new(3, 2, EightDataClient::new(2)).unwrap();
+        parsed.write_into(&mut written);
+        assert_eq!(&[0b_1100_0000, 0b_1010_0000], written.as_vec());
+
+        let reversed = to_container(&image, ContainerOptions { bit_order: Some(BitOrder::MsbFirst), ..Default::default() }).unwrap();
+        assert_eq!(&[0b_1100_0000, 0b_1000_0000, 0b_0100_0000], &reversed[16..]);
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), parse_container(&reversed).map(|_| ()));
+        assert_eq!(image.as_vec(), decode_container(&reversed, &mut [0; 3]).unwrap().as_vec());
+        assert_eq!(&[0b_1100_0000, 0b_1000_0000, 0b_0100_0000], &reversed[16..]);
+    }
+
+    #[test]
+    fn test_decode_container() {
+        let mut image = HorizontalEightPxUintEight::new(64, 4, EightDataClient::new(32)).unwrap();
+        image.fill((0, 1, 64, 2), Mono::One);
+
+        let options = ContainerOptions { bit_order: None, compression: Compression::PackBits };
+        let container = to_container(&image, options).unwrap();
+        assert!(container.len() < 16 + 32);
+
+        let mut buffer = [0; 32];
+        let decoded = decode_container(&container, &mut buffer).unwrap();
+        assert_eq!(Layout::Horizontal, decoded.layout());
+        assert_eq!(Mono::One, decoded.pixel(10, 2));
+        assert_eq!(image.as_vec(), decoded.as_vec());
+
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), parse_container(&container).map(|_| ()));
+        assert_eq!(Err(EightPxUintEightError::Overflow((31, 32))), decode_container(&container, &mut [0; 31]).map(|_| ()));
+    }
+
+    #[test]
+    fn test_short_runs() {
+        let mut image = HorizontalEightPxUintEight::new(8, 30, EightDataClient::new(30)).unwrap();
+        for y in (0..30).step_by(3) {
+            image.fill((0, y, 8, 1), Mono::One);
+        }
+
+        let options = ContainerOptions { bit_order: None, compression: Compression::PackBits };
+        let container = to_container(&image, options).unwrap();
+        assert_eq!(16 + 31, container.len());
+        assert_eq!(image.as_vec(), decode_container(&container, &mut [0; 30]).unwrap().as_vec());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let image = vertical(3, 2, &PIXELS);
+        let container = to_container(&image, ContainerOptions::default()).unwrap();
+
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), ContainerHeader::parse(b"E8PX"));
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), ContainerHeader::parse(&[0; 16]));
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), ContainerHeader::parse(&container[..18]));
+
+        let mut huge = container.clone();
+        huge[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(ContainerHeader::parse(&huge).is_err());
+
+        let mut buffer = [0; 3];
+        assert_eq!(Err(EightPxUintEightError::Overflow((18, 19))), write_container(&image, ContainerOptions::default(), &mut [0; 18]));
+        assert_eq!(Ok(19), write_container(&image, ContainerOptions::default(), &mut [0; 19]));
+        assert!(decode_container(&container, &mut buffer).is_ok());
+    }
+}
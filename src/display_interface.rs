@@ -0,0 +1,225 @@
+use crate::*;
+
+/// Transport to a display controller. ex: SPI with a DC pin, I2C with control bytes
+pub trait DisplayInterface {
+    type Error;
+
+    fn send_command(&mut self, command: u8) -> Result<(), Self::Error>;
+    fn send_data(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Send commands in order. Some controllers take parameters as commands. ex: SSD1306
+    fn send_commands(&mut self, commands: &[u8]) -> Result<(), Self::Error> {
+        commands
+            .iter()
+            .try_for_each(|command| self.send_command(*command))
+    }
+
+    /// Send a command and its parameters as data.
+    fn send_command_with_data(&mut self, command: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.send_command(command)?;
+        self.send_data(data)
+    }
+
+    /// Wait while the controller is busy. Nothing to wait without a busy pin.
+    fn wait_busy(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Reset the controller. Nothing to do without a reset pin.
+    fn reset(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Controller that writes data into a window of its RAM.
+///
+/// The window is a rectangle of cells that `part_vec` returns.
+/// A cell is a byte in a row for `Horizontal` and a column of a page for `Vertical`.
+pub trait RamWindow {
+    fn set_window<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        window: Rectangle,
+    ) -> Result<(), I::Error>;
+
+    /// Called before each row of the window. ex: set the page address
+    fn start_row<I: DisplayInterface>(
+        &mut self,
+        _interface: &mut I,
+        _window: Rectangle,
+        _row: usize,
+    ) -> Result<(), I::Error> {
+        Ok(())
+    }
+}
+
+/// Bytes sent at once by `flush_by`.
+const FLUSH_CHUNK_LENGTH: usize = 32;
+
+fn flush_rows<I: DisplayInterface>(
+    image: &impl EightPxUintEight,
+    xywh: impl ActAsXywh,
+    controller: &mut impl RamWindow,
+    interface: &mut I,
+    mut send: impl FnMut(&mut I, &[u8]) -> Result<(), I::Error>,
+) -> Result<Rectangle, I::Error> {
+    let (x, y, width, height) = xywh.xywh();
+
+    if x >= image.width() || y >= image.height() || width == 0 || height == 0 {
+        return Ok(Rectangle::new(0, 0, 0, 0));
+    }
+
+    let Part {
+        src_x,
+        src_y,
+        src_width,
+        result_width,
+        result_height,
+        ..
+    } = image.compute_part(xywh);
+    let window = Rectangle::new(src_x, src_y, result_width, result_height);
+    let src = image.eight_data().core();
+
+    controller.set_window(interface, window)?;
+
+    for row in 0..result_height {
+        let start = src_width * (src_y + row) + src_x;
+
+        controller.start_row(interface, window, row)?;
+        send(interface, &src[start..start + result_width])?;
+    }
+
+    Ok(window)
+}
+
+/// Send the rectangle of the image. The bytes of each row are the same as `part_vec`.
+///
+/// Return the window that is sent.
+pub fn flush<I: DisplayInterface>(
+    image: &impl EightPxUintEight,
+    xywh: impl ActAsXywh,
+    controller: &mut impl RamWindow,
+    interface: &mut I,
+) -> Result<Rectangle, I::Error> {
+    flush_rows(image, xywh, controller, interface, |interface, row| {
+        interface.send_data(row)
+    })
+}
+
+/// Same as `flush` but every byte is mapped before sending. ex: e-paper that has 0 as black
+pub fn flush_by<I: DisplayInterface>(
+    image: &impl EightPxUintEight,
+    xywh: impl ActAsXywh,
+    controller: &mut impl RamWindow,
+    interface: &mut I,
+    map: impl Fn(u8) -> u8,
+) -> Result<Rectangle, I::Error> {
+    let mut buffer = [0; FLUSH_CHUNK_LENGTH];
+
+    flush_rows(image, xywh, controller, interface, |interface, row| {
+        for chunk in row.chunks(FLUSH_CHUNK_LENGTH) {
+            let mapped = &mut buffer[..chunk.len()];
+            mapped.iter_mut().zip(chunk).for_each(|(m, c)| *m = map(*c));
+            interface.send_data(mapped)?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+
+    /// Window commands of SSD1306 in horizontal addressing.
+    struct Controller;
+
+    impl RamWindow for Controller {
+        fn set_window<I: DisplayInterface>(&mut self, interface: &mut I, window: Rectangle) -> Result<(), I::Error> {
+            interface.send_command_with_data(0x21, &[window.x as u8, (window.x + window.width - 1) as u8])?;
+            interface.send_command_with_data(0x22, &[window.y as u8, (window.y + window.height - 1) as u8])
+        }
+    }
+
+    /// Page addressing that needs the address of every page.
+    struct PageController;
+
+    impl RamWindow for PageController {
+        fn set_window<I: DisplayInterface>(&mut self, _interface: &mut I, _window: Rectangle) -> Result<(), I::Error> {
+            Ok(())
+        }
+
+        fn start_row<I: DisplayInterface>(&mut self, interface: &mut I, window: Rectangle, row: usize) -> Result<(), I::Error> {
+            interface.send_command(0xb0 | (window.y + row) as u8)
+        }
+    }
+
+    #[test]
+    fn test_flush() {
+        let mut image = VerticalEightPxUintEight::new(4, 16, EightDataClient::new(8)).unwrap();
+        image.fill((1, 0, 2, 9), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        let window = flush(&image, (1, 8, 3, 2), &mut Controller, &mut interface).unwrap();
+
+        assert_eq!(Rectangle::new(1, 1, 3, 1), window);
+        assert_eq!(
+            vec![
+                Transfer::Command(0x21),
+                Transfer::Data(vec![1, 3]),
+                Transfer::Command(0x22),
+                Transfer::Data(vec![1, 1]),
+                Transfer::Data(vec![0b_0000_0001, 0b_0000_0001, 0b_0000_0000]),
+            ],
+            interface.transfers
+        );
+    }
+
+    #[test]
+    fn test_flush_rows() {
+        let mut image = VerticalEightPxUintEight::new(4, 16, EightDataClient::new(8)).unwrap();
+        image.fill((1, 0, 2, 9), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        flush(&image, (0, 0, 4, 16), &mut PageController, &mut interface).unwrap();
+
+        assert_eq!(
+            vec![
+                Transfer::Command(0xb0),
+                Transfer::Data(vec![0b_0000_0000, 0b_1111_1111, 0b_1111_1111, 0b_0000_0000]),
+                Transfer::Command(0xb1),
+                Transfer::Data(vec![0b_0000_0000, 0b_0000_0001, 0b_0000_0001, 0b_0000_0000]),
+            ],
+            interface.transfers
+        );
+    }
+
+    #[test]
+    fn test_flush_by() {
+        let mut image = HorizontalEightPxUintEight::new(320, 1, EightDataClient::new(40)).unwrap();
+        image.fill((0, 0, 8, 1), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        flush_by(&image, (0, 0, 320, 1), &mut Controller, &mut interface, |byte| !byte).unwrap();
+
+        // a row is sent in chunks
+        let mut first = vec![0b_1111_1111; 32];
+        first[0] = 0b_0000_0000;
+        assert_eq!(Transfer::Data(first), interface.transfers[4]);
+        assert_eq!(Transfer::Data(vec![0b_1111_1111; 8]), interface.transfers[5]);
+    }
+
+    #[test]
+    fn test_flush_outside() {
+        let mut image = VerticalEightPxUintEight::new(4, 16, EightDataClient::new(8)).unwrap();
+        image.fill((1, 0, 2, 9), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+
+        assert_eq!(Ok(Rectangle::new(0, 0, 0, 0)), flush(&image, (4, 0, 1, 1), &mut Controller, &mut interface));
+        assert_eq!(Ok(Rectangle::new(0, 0, 0, 0)), flush(&image, (0, 0, 0, 1), &mut Controller, &mut interface));
+        assert!(interface.transfers.is_empty());
+    }
+}
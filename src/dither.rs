@@ -0,0 +1,414 @@
+use crate::*;
+use core::cmp::{max, min};
+
+/// Error diffusion kernels.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Diffusion {
+    FloydSteinberg,
+    Atkinson,
+    Stucki,
+    JarvisJudiceNinke,
+}
+
+// (dx, dy, weight)
+type Kernel = &'static [(isize, usize, i32)];
+
+const FLOYD_STEINBERG: Kernel = &[(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)];
+
+// Atkinson propagates only 3/4 of the error.
+const ATKINSON: Kernel = &[
+    (1, 0, 1),
+    (2, 0, 1),
+    (-1, 1, 1),
+    (0, 1, 1),
+    (1, 1, 1),
+    (0, 2, 1),
+];
+
+const STUCKI: Kernel = &[
+    (1, 0, 8),
+    (2, 0, 4),
+    (-2, 1, 2),
+    (-1, 1, 4),
+    (0, 1, 8),
+    (1, 1, 4),
+    (2, 1, 2),
+    (-2, 2, 1),
+    (-1, 2, 2),
+    (0, 2, 4),
+    (1, 2, 2),
+    (2, 2, 1),
+];
+
+const JARVIS_JUDICE_NINKE: Kernel = &[
+    (1, 0, 7),
+    (2, 0, 5),
+    (-2, 1, 3),
+    (-1, 1, 5),
+    (0, 1, 7),
+    (1, 1, 5),
+    (2, 1, 3),
+    (-2, 2, 1),
+    (-1, 2, 3),
+    (0, 2, 5),
+    (1, 2, 3),
+    (2, 2, 1),
+];
+
+impl Diffusion {
+    /// Return rows of errors that the kernel needs, including the current row.
+    pub fn rows(&self) -> usize {
+        match self {
+            Diffusion::FloydSteinberg => 2,
+            Diffusion::Atkinson | Diffusion::Stucki | Diffusion::JarvisJudiceNinke => 3,
+        }
+    }
+
+    /// Return length of the error buffer for the width.
+    pub fn errors_length(&self, width: usize) -> usize {
+        self.rows() * width
+    }
+
+    fn kernel(&self) -> (Kernel, i32) {
+        match self {
+            Diffusion::FloydSteinberg => (FLOYD_STEINBERG, 16),
+            Diffusion::Atkinson => (ATKINSON, 8),
+            Diffusion::Stucki => (STUCKI, 42),
+            Diffusion::JarvisJudiceNinke => (JARVIS_JUDICE_NINKE, 48),
+        }
+    }
+}
+
+/// Draw `src` with error diffusion. Dark pixels become `Mono::One`.
+///
+/// `errors` is a work buffer of `diffusion.errors_length(width)` at least.
+/// With `serpentine`, odd rows are scanned from right to left.
+pub fn diffuse(
+    image: &mut impl EightPxUintEight,
+    xywh: impl ActAsXywh,
+    src: &[impl ActAsLuma],
+    diffusion: Diffusion,
+    serpentine: bool,
+    errors: &mut [i16],
+) -> EightPxUintEightResult<()> {
+    let (x, y, width, height) = xywh.xywh();
+    let rows = diffusion.rows();
+    let (kernel, divisor) = diffusion.kernel();
+
+    if errors.len() < diffusion.errors_length(width) {
+        return Err(EightPxUintEightError::InvalidLengthData);
+    }
+
+    let errors = &mut errors[..diffusion.errors_length(width)];
+    errors.iter_mut().for_each(|e| *e = 0);
+
+    for step_y in 0..height {
+        let reverse = serpentine && step_y % 2 == 1;
+
+        for n in 0..width {
+            let step_x = match reverse {
+                true => width - 1 - n,
+                false => n,
+            };
+
+            let index = width * step_y + step_x;
+            let luma = match src.get(index) {
+                None => return Err(EightPxUintEightError::Overflow((src.len(), index))),
+                Some(n) => n,
+            }
+            .luma();
+
+            let row = (step_y % rows) * width;
+            let value = luma as i32 + errors[row + step_x] as i32;
+            let (color, error) = match value < 128 {
+                true => (Mono::One, value),
+                false => (Mono::Zero, value - 255),
+            };
+
+            for (dx, dy, weight) in kernel {
+                let dx = match reverse {
+                    true => -dx,
+                    false => *dx,
+                };
+                let target_x = step_x as isize + dx;
+
+                if target_x < 0 || target_x >= width as isize {
+                    continue;
+                }
+
+                let i = ((step_y + dy) % rows) * width + target_x as usize;
+                let spread = error * weight / divisor;
+                errors[i] =
+                    (errors[i] as i32 + spread).clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+            }
+
+            // discard pixels that overflow
+            if x + step_x < image.width() && y + step_y < image.height() {
+                image.draw(x + step_x, y + step_y, color);
+            }
+        }
+
+        // the row is reused for the row after the kernel
+        let row = (step_y % rows) * width;
+        errors[row..row + width].iter_mut().for_each(|e| *e = 0);
+    }
+
+    Ok(())
+}
+
+/// Threshold map of ordered dithering. Pixels darker than the threshold become `Mono::One`.
+///
+/// Bayer matrices are provided, and any map like blue noise can be given.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct ThresholdMap<'a> {
+    width: usize,
+    height: usize,
+    thresholds: &'a [u8],
+}
+
+#[rustfmt::skip]
+pub const BAYER_2: ThresholdMap<'static> = ThresholdMap {
+    width: 2,
+    height: 2,
+    thresholds: &[
+         32, 160,
+        224,  96,
+    ],
+};
+
+#[rustfmt::skip]
+pub const BAYER_4: ThresholdMap<'static> = ThresholdMap {
+    width: 4,
+    height: 4,
+    thresholds: &[
+          8, 136,  40, 168,
+        200,  72, 232, 104,
+         56, 184,  24, 152,
+        248, 120, 216,  88,
+    ],
+};
+
+#[rustfmt::skip]
+pub const BAYER_8: ThresholdMap<'static> = ThresholdMap {
+    width: 8,
+    height: 8,
+    thresholds: &[
+          2, 130,  34, 162,  10, 138,  42, 170,
+        194,  66, 226,  98, 202,  74, 234, 106,
+         50, 178,  18, 146,  58, 186,  26, 154,
+        242, 114, 210,  82, 250, 122, 218,  90,
+         14, 142,  46, 174,   6, 134,  38, 166,
+        206,  78, 238, 110, 198,  70, 230, 102,
+         62, 190,  30, 158,  54, 182,  22, 150,
+        254, 126, 222,  94, 246, 118, 214,  86,
+    ],
+};
+
+impl<'a> ThresholdMap<'a> {
+    pub fn new(width: usize, height: usize, thresholds: &'a [u8]) -> EightPxUintEightResult<Self> {
+        if width.checked_mul(height) != Some(thresholds.len()) || thresholds.is_empty() {
+            return Err(EightPxUintEightError::InvalidLengthData);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            thresholds,
+        })
+    }
+
+    /// Return the threshold at (x, y) of the image. The map is tiled.
+    pub fn threshold(&self, x: usize, y: usize) -> u8 {
+        self.thresholds[self.width * (y % self.height) + x % self.width]
+    }
+}
+
+/// Draw a row from (x, y) with ordered dithering.
+///
+/// This needs no buffer, so rows can be given one by one. Pixels that overflow are discarded.
+pub fn ordered_row(
+    image: &mut impl EightPxUintEight,
+    x: usize,
+    y: usize,
+    row: &[impl ActAsLuma],
+    map: &ThresholdMap,
+) {
+    // avoid unsigned subtract overflow
+    if x > image.width() || y >= image.height() {
+        return;
+    }
+
+    for (step_x, pixel) in row.iter().take(image.width() - x).enumerate() {
+        let color = match pixel.luma() < map.threshold(x + step_x, y) {
+            true => Mono::One,
+            false => Mono::Zero,
+        };
+
+        image.draw(x + step_x, y, color);
+    }
+}
+
+/// Draw `src` with ordered dithering.
+pub fn ordered(
+    image: &mut impl EightPxUintEight,
+    xywh: impl ActAsXywh,
+    src: &[impl ActAsLuma],
+    map: &ThresholdMap,
+) -> EightPxUintEightResult<()> {
+    let (x, y, width, height) = xywh.xywh();
+
+    // avoid unsigned subtract overflow
+    if x > image.width() || y > image.height() {
+        return Ok(());
+    }
+
+    // discard pixels that overflow
+    let columns = min(width, image.width() - x);
+    for step_y in 0..min(height, image.height() - y) {
+        let start = width * step_y;
+        let row = match src.get(start..start + columns) {
+            Some(row) => row,
+            None => {
+                let index = max(start, src.len());
+                return Err(EightPxUintEightError::Overflow((src.len(), index)));
+            }
+        };
+
+        ordered_row(image, x, y + step_y, row, map);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::EightDataClient;
+
+    fn count(image: &impl EightPxUintEight) -> usize {
+        image.as_vec().iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    #[test]
+    fn test_floyd_steinberg() {
+        let mut image = HorizontalEightPxUintEight::new(4, 2, EightDataClient::new(2)).unwrap();
+        let mut errors = vec![0; Diffusion::FloydSteinberg.errors_length(4)];
+
+        diffuse(&mut image, (0, 0, 4, 2), &[128u8; 8], Diffusion::FloydSteinberg, false, &mut errors).unwrap();
+        assert_eq!(
+            [
+                0b_0101_0000,
+                0b_1010_0000,
+            ],
+            image.as_vec()
+        );
+    }
+
+    #[test]
+    fn test_solid() {
+        for diffusion in [Diffusion::FloydSteinberg, Diffusion::Atkinson, Diffusion::Stucki, Diffusion::JarvisJudiceNinke] {
+            let mut errors = vec![0; diffusion.errors_length(16)];
+
+            let mut image = HorizontalEightPxUintEight::new(16, 16, EightDataClient::new(32)).unwrap();
+            diffuse(&mut image, (0, 0, 16, 16), &[0u8; 256], diffusion, true, &mut errors).unwrap();
+            assert_eq!(256, count(&image));
+
+            diffuse(&mut image, (0, 0, 16, 16), &[255u8; 256], diffusion, true, &mut errors).unwrap();
+            assert_eq!(0, count(&image));
+        }
+    }
+
+    #[test]
+    fn test_gray_ratio() {
+        for diffusion in [Diffusion::FloydSteinberg, Diffusion::Stucki, Diffusion::JarvisJudiceNinke] {
+            for serpentine in [false, true] {
+                let mut errors = vec![0; diffusion.errors_length(32)];
+                let mut image = HorizontalEightPxUintEight::new(32, 32, EightDataClient::new(128)).unwrap();
+
+                // a quarter of pixels should be black
+                diffuse(&mut image, (0, 0, 32, 32), &[(192u8, 192u8, 192u8); 1024], diffusion, serpentine, &mut errors).unwrap();
+                let black = count(&image);
+                assert!((218..=294).contains(&black), "{:?} {}", diffusion, black);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ordered() {
+        let mut image = HorizontalEightPxUintEight::new(4, 4, EightDataClient::new(4)).unwrap();
+
+        ordered(&mut image, (0, 0, 4, 4), &[128u8; 16], &BAYER_2).unwrap();
+        assert_eq!(
+            [
+                0b_0101_0000,
+                0b_1010_0000,
+                0b_0101_0000,
+                0b_1010_0000,
+            ],
+            image.as_vec()
+        );
+
+        ordered(&mut image, (0, 0, 4, 4), &[128u8; 16], &BAYER_4).unwrap();
+        assert_eq!(8, count(&image));
+
+        ordered(&mut image, (0, 0, 4, 4), &[0u8; 16], &BAYER_8).unwrap();
+        assert_eq!(16, count(&image));
+
+        // empty rectangles draw nothing
+        assert_eq!(Ok(()), ordered(&mut image, (0, 0, 0, 4), &[0u8; 0], &BAYER_8));
+        assert_eq!(Ok(()), ordered(&mut image, (0, 0, 4, 0), &[0u8; 0], &BAYER_8));
+
+        // the first index out of the source. pixels out of the image are not read
+        assert_eq!(
+            Err(EightPxUintEightError::Overflow((7, 7))),
+            ordered(&mut image, (0, 0, 4, 4), &[0u8; 7], &BAYER_8)
+        );
+        assert_eq!(
+            Err(EightPxUintEightError::Overflow((5, 6))),
+            ordered(&mut image, (2, 0, 6, 4), &[0u8; 5], &BAYER_8)
+        );
+    }
+
+    #[test]
+    fn test_ordered_row() {
+        let map = ThresholdMap::new(3, 1, &[64, 128, 192]).unwrap();
+        let mut image = HorizontalEightPxUintEight::new(8, 2, EightDataClient::new(2)).unwrap();
+
+        // the map follows the position in the image
+        ordered_row(&mut image, 0, 0, &[100u8; 8], &map);
+        ordered_row(&mut image, 2, 1, &[100u8; 8], &map);
+        assert_eq!(
+            [
+                0b_0110_1101,
+                0b_0010_1101,
+            ],
+            image.as_vec()
+        );
+    }
+
+    #[test]
+    fn test_invalid_threshold_map() {
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), ThresholdMap::new(2, 2, &[0; 3]));
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), ThresholdMap::new(0, 0, &[]));
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), ThresholdMap::new(usize::MAX, 2, &[0; 2]));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut image = HorizontalEightPxUintEight::new(4, 2, EightDataClient::new(2)).unwrap();
+
+        let mut errors = vec![0; 4];
+        assert_eq!(
+            Err(EightPxUintEightError::InvalidLengthData),
+            diffuse(&mut image, (0, 0, 4, 2), &[0u8; 8], Diffusion::FloydSteinberg, false, &mut errors)
+        );
+
+        let mut errors = vec![0; 8];
+        assert_eq!(
+            Err(EightPxUintEightError::Overflow((7, 7))),
+            diffuse(&mut image, (0, 0, 4, 2), &[0u8; 7], Diffusion::FloydSteinberg, false, &mut errors)
+        );
+    }
+}
@@ -0,0 +1,13 @@
+mod pcd8544;
+mod sharp_memory;
+mod ssd1306;
+mod ssd1680;
+mod st7565;
+mod uc8151;
+
+pub use pcd8544::*;
+pub use sharp_memory::*;
+pub use ssd1306::*;
+pub use ssd1680::*;
+pub use st7565::*;
+pub use uc8151::*;
@@ -0,0 +1,166 @@
+use crate::*;
+
+const FUNCTION_SET: u8 = 0x20;
+const EXTENDED: u8 = 0x01;
+
+/// PCD8544 LCD of Nokia 5110 and 3310, 84 x 48. Its banks are pages of `VerticalEightPxUintEight`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Pcd8544 {
+    contrast: u8,
+    bias: u8,
+}
+
+impl Default for Pcd8544 {
+    fn default() -> Self {
+        Self {
+            contrast: 0x3f,
+            bias: 0x04,
+        }
+    }
+}
+
+impl Pcd8544 {
+    pub const WIDTH: usize = 84;
+    pub const HEIGHT: usize = 48;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contrast(&self) -> u8 {
+        self.contrast
+    }
+
+    pub fn bias(&self) -> u8 {
+        self.bias
+    }
+
+    /// Send commands of the extended instruction set.
+    fn extended<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        commands: &[u8],
+    ) -> Result<(), I::Error> {
+        interface.send_command(FUNCTION_SET | EXTENDED)?;
+        interface.send_commands(commands)?;
+        interface.send_command(FUNCTION_SET)
+    }
+
+    /// Configure the controller and turn the display on.
+    pub fn init<I: DisplayInterface>(&mut self, interface: &mut I) -> Result<(), I::Error> {
+        interface.reset()?;
+        self.extended(
+            interface,
+            &[
+                0x80 | self.contrast, // operating voltage
+                0x04,                 // temperature coefficient
+                0x10 | self.bias,
+            ],
+        )?;
+        interface.send_command(0x0c) // normal mode
+    }
+
+    /// Operating voltage `0..=127`.
+    pub fn set_contrast<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        contrast: u8,
+    ) -> Result<(), I::Error> {
+        self.contrast = contrast & 0x7f;
+        self.extended(interface, &[0x80 | self.contrast])
+    }
+
+    /// Bias system `0..=7`. 3 is 1:48 of the datasheet for 48 rows.
+    /// The default 4 (1:40/1:34) is common for Nokia 5110 modules.
+    pub fn set_bias<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        bias: u8,
+    ) -> Result<(), I::Error> {
+        self.bias = bias & 0x07;
+        self.extended(interface, &[0x10 | self.bias])
+    }
+
+    pub fn set_inverted<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        inverted: bool,
+    ) -> Result<(), I::Error> {
+        interface.send_command(match inverted {
+            true => 0x0d,
+            false => 0x0c,
+        })
+    }
+
+    /// Send the banks that have the rectangle of the image.
+    pub fn flush<I: DisplayInterface, D: EightData>(
+        &mut self,
+        interface: &mut I,
+        image: &VerticalEightPxUintEight<D>,
+        xywh: impl ActAsXywh,
+    ) -> Result<Rectangle, I::Error> {
+        flush(image, xywh, self, interface)
+    }
+}
+
+impl RamWindow for Pcd8544 {
+    fn set_window<I: DisplayInterface>(
+        &mut self,
+        _interface: &mut I,
+        _window: Rectangle,
+    ) -> Result<(), I::Error> {
+        Ok(())
+    }
+
+    fn start_row<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        window: Rectangle,
+        row: usize,
+    ) -> Result<(), I::Error> {
+        interface.send_commands(&[0x40 | (window.y + row) as u8, 0x80 | window.x as u8])
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+
+    #[test]
+    fn test_init() {
+        let mut interface = RecordingInterface::default();
+        let mut lcd = Pcd8544::new();
+        lcd.init(&mut interface).unwrap();
+
+        assert_eq!(vec![0x21, 0xbf, 0x04, 0x14, 0x20, 0x0c], interface.commands());
+
+        interface.clear();
+        lcd.set_contrast(&mut interface, 0x50).unwrap();
+        lcd.set_bias(&mut interface, 3).unwrap();
+        assert_eq!(vec![0x21, 0xd0, 0x20, 0x21, 0x13, 0x20], interface.commands());
+        assert_eq!((0x50, 3), (lcd.contrast(), lcd.bias()));
+    }
+
+    #[test]
+    fn test_flush() {
+        let mut image = VerticalEightPxUintEight::new(84, 48, EightDataClient::new(84 * 6)).unwrap();
+        image.fill((80, 40, 4, 8), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        let window = Pcd8544::new().flush(&mut interface, &image, (80, 39, 10, 10)).unwrap();
+
+        assert_eq!(Rectangle::new(80, 4, 4, 2), window);
+        assert_eq!(
+            vec![
+                Transfer::Command(0x44), Transfer::Command(0xd0),
+                Transfer::Data(vec![0b_0000_0000; 4]),
+                Transfer::Command(0x45), Transfer::Command(0xd0),
+                Transfer::Data(vec![0b_1111_1111; 4]),
+            ],
+            interface.transfers
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use crate::*;
+
+const WRITE: u8 = 0b_1000_0000;
+const VCOM: u8 = 0b_0100_0000;
+const CLEAR: u8 = 0b_0010_0000;
+
+/// Line addresses are 8 bits from 1.
+pub const SHARP_MEMORY_MAX_LINES: usize = 255;
+
+/// Sharp Memory LCD (LS013B7DH03, LS027B7DH01 etc.) write-line protocol for MSB-first SPI.
+///
+/// A line is its address, `HorizontalEightPxUintEight` bytes and a trailer byte.
+/// `Mono::One` is black. Data of the LCD has 0 as black, so bytes are inverted.
+///
+/// VCOM must be inverted about once a second. Toggle it and send either lines or `vcom_command`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub struct SharpMemory {
+    vcom: bool,
+}
+
+impl SharpMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn vcom(&self) -> bool {
+        self.vcom
+    }
+
+    pub fn toggle_vcom(&mut self) {
+        self.vcom = !self.vcom;
+    }
+
+    fn mode(&self, mode: u8) -> u8 {
+        match self.vcom {
+            true => mode | VCOM,
+            false => mode,
+        }
+    }
+
+    /// Length of `lines` lines of the width.
+    pub fn encoded_length(width: usize, lines: usize) -> usize {
+        1 + lines * (1 + compute_eight_length(width) + 1) + 1
+    }
+
+    /// Keep the memory and only send VCOM.
+    pub fn vcom_command(&self) -> [u8; 2] {
+        [self.mode(0), 0]
+    }
+
+    /// Clear all pixels to white.
+    pub fn clear_command(&self) -> [u8; 2] {
+        [self.mode(CLEAR), 0]
+    }
+
+    /// Encode the lines into `dst`. ex: `0..height` for all, or only dirty lines.
+    ///
+    /// Return the length written. Images taller than `SHARP_MEMORY_MAX_LINES` are an error.
+    pub fn encode_lines<D: EightData>(
+        &self,
+        image: &HorizontalEightPxUintEight<D>,
+        lines: impl IntoIterator<Item = usize>,
+        dst: &mut [u8],
+    ) -> EightPxUintEightResult<usize> {
+        if image.height() > SHARP_MEMORY_MAX_LINES {
+            return Err(EightPxUintEightError::InvalidLengthData);
+        }
+
+        let length = image.eight_length();
+        let src = image.as_vec();
+
+        let mut output = Output::new(dst);
+        output.put(&[self.mode(WRITE)])?;
+
+        for line in lines {
+            if line >= image.height() {
+                return Err(EightPxUintEightError::Overflow((image.height(), line)));
+            }
+
+            // line addresses start at 1 and are sent LSB first
+            output.put(&[((line + 1) as u8).reverse_bits()])?;
+            for byte in &src[length * line..length * (line + 1)] {
+                output.put(&[!byte])?;
+            }
+            output.put(&[0])?;
+        }
+
+        output.put(&[0])
+    }
+
+    /// Encode all lines into `dst`.
+    pub fn encode<D: EightData>(
+        &self,
+        image: &HorizontalEightPxUintEight<D>,
+        dst: &mut [u8],
+    ) -> EightPxUintEightResult<usize> {
+        self.encode_lines(image, 0..image.height(), dst)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn lines_to_vec<D: EightData>(
+        &self,
+        image: &HorizontalEightPxUintEight<D>,
+        lines: impl IntoIterator<Item = usize>,
+    ) -> EightPxUintEightResult<Vec<u8>> {
+        let lines = lines.into_iter().collect::<Vec<_>>();
+        let mut dst = vec![0; Self::encoded_length(image.width(), lines.len())];
+        self.encode_lines(image, lines, &mut dst)?;
+
+        Ok(dst)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+
+    const PIXELS: [u8; 48] = [
+        1, 1, 1, 1, 1, 1, 1, 1,  0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0,  0, 0, 0, 0, 0, 0, 0, 0,
+        1, 0, 0, 0, 0, 0, 0, 0,  0, 0, 0, 0, 0, 0, 0, 1,
+    ];
+
+    #[test]
+    fn test_encode() {
+        let image = horizontal(16, 3, &PIXELS);
+        let mut dst = [0; 14];
+        assert_eq!(Ok(14), SharpMemory::new().encode(&image, &mut dst));
+        assert_eq!(
+            [
+                0b_1000_0000,
+                0b_1000_0000, 0b_0000_0000, 0b_1111_1111, 0,
+                0b_0100_0000, 0b_1111_1111, 0b_1111_1111, 0,
+                0b_1100_0000, 0b_0111_1111, 0b_1111_1110, 0,
+                0,
+            ],
+            dst
+        );
+    }
+
+    #[test]
+    fn test_dirty_lines() {
+        let image = horizontal(16, 3, &PIXELS);
+        let mut lcd = SharpMemory::new();
+        lcd.toggle_vcom();
+
+        assert_eq!(
+            vec![0b_1100_0000, 0b_1100_0000, 0b_0111_1111, 0b_1111_1110, 0, 0],
+            lcd.lines_to_vec(&image, [2]).unwrap()
+        );
+        assert_eq!(
+            Err(EightPxUintEightError::Overflow((3, 3))),
+            lcd.lines_to_vec(&image, [3])
+        );
+        assert_eq!(
+            Err(EightPxUintEightError::Overflow((5, 6))),
+            lcd.encode_lines(&image, [0], &mut [0; 5])
+        );
+    }
+
+    #[test]
+    fn test_tall_panel() {
+        let lcd = SharpMemory::new();
+        let image = HorizontalEightPxUintEight::new(8, 255, EightDataClient::new(255)).unwrap();
+        let encoded = lcd.lines_to_vec(&image, [254]).unwrap();
+        assert_eq!(0b_1111_1111, encoded[1]);
+
+        let image = HorizontalEightPxUintEight::new(8, 256, EightDataClient::new(256)).unwrap();
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), lcd.lines_to_vec(&image, [0]));
+    }
+
+    #[test]
+    fn test_vcom() {
+        let mut lcd = SharpMemory::new();
+        assert_eq!([0b_0000_0000, 0], lcd.vcom_command());
+        assert_eq!([0b_0010_0000, 0], lcd.clear_command());
+
+        lcd.toggle_vcom();
+        assert!(lcd.vcom());
+        assert_eq!([0b_0100_0000, 0], lcd.vcom_command());
+        assert_eq!([0b_0110_0000, 0], lcd.clear_command());
+    }
+}
@@ -0,0 +1,222 @@
+use crate::*;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum OledController {
+    Ssd1306,
+    /// 132 columns of RAM with 128 pixels at column 2. Only page addressing.
+    Sh1106,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum OledSize {
+    Display128x64,
+    Display128x32,
+}
+
+impl OledSize {
+    pub fn width(&self) -> usize {
+        128
+    }
+
+    pub fn height(&self) -> usize {
+        match self {
+            OledSize::Display128x64 => 64,
+            OledSize::Display128x32 => 32,
+        }
+    }
+
+    fn com_pins(&self) -> u8 {
+        match self {
+            OledSize::Display128x64 => 0x12,
+            OledSize::Display128x32 => 0x02,
+        }
+    }
+}
+
+/// SSD1306 and SH1106 OLED. RAM is written by page addressing, so its pages are `VerticalEightPxUintEight` as is.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Ssd1306 {
+    controller: OledController,
+    size: OledSize,
+}
+
+impl Ssd1306 {
+    pub fn new(controller: OledController, size: OledSize) -> Self {
+        Self { controller, size }
+    }
+
+    pub fn controller(&self) -> OledController {
+        self.controller
+    }
+
+    pub fn size(&self) -> OledSize {
+        self.size
+    }
+
+    fn column_offset(&self) -> usize {
+        match self.controller {
+            OledController::Ssd1306 => 0,
+            OledController::Sh1106 => 2,
+        }
+    }
+
+    /// Configure the controller and turn the display on.
+    #[rustfmt::skip]
+    pub fn init<I: DisplayInterface>(&mut self, interface: &mut I) -> Result<(), I::Error> {
+        interface.reset()?;
+        interface.send_commands(&[
+            0xae, // display off
+            0xd5, 0x80, // clock
+            0xa8, self.size.height() as u8 - 1, // multiplex
+            0xd3, 0x00, // display offset
+            0x40, // start line
+        ])?;
+
+        match self.controller {
+            OledController::Ssd1306 => interface.send_commands(&[
+                0x8d, 0x14, // charge pump
+                0x20, 0x02, // page addressing
+            ])?,
+            OledController::Sh1106 => interface.send_commands(&[
+                0xad, 0x8b, // DC-DC
+            ])?,
+        }
+
+        interface.send_commands(&[
+            0xa1, // segment remap
+            0xc8, // COM scan from the last
+            0xda, self.size.com_pins(), // COM pins
+            0x81, 0xcf, // contrast
+            0xd9, 0xf1, // pre-charge
+            0xdb, 0x40, // VCOMH
+            0xa4, // display RAM
+            0xa6, // not inverted
+            0xaf, // display on
+        ])
+    }
+
+    pub fn set_contrast<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        contrast: u8,
+    ) -> Result<(), I::Error> {
+        interface.send_commands(&[0x81, contrast])
+    }
+
+    pub fn set_display_on<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        on: bool,
+    ) -> Result<(), I::Error> {
+        interface.send_command(match on {
+            true => 0xaf,
+            false => 0xae,
+        })
+    }
+
+    /// Send the pages that have the rectangle of the image.
+    pub fn flush<I: DisplayInterface, D: EightData>(
+        &mut self,
+        interface: &mut I,
+        image: &VerticalEightPxUintEight<D>,
+        xywh: impl ActAsXywh,
+    ) -> Result<Rectangle, I::Error> {
+        flush(image, xywh, self, interface)
+    }
+}
+
+impl RamWindow for Ssd1306 {
+    fn set_window<I: DisplayInterface>(
+        &mut self,
+        _interface: &mut I,
+        _window: Rectangle,
+    ) -> Result<(), I::Error> {
+        Ok(())
+    }
+
+    fn start_row<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        window: Rectangle,
+        row: usize,
+    ) -> Result<(), I::Error> {
+        let column = window.x + self.column_offset();
+
+        interface.send_commands(&[
+            0xb0 | (window.y + row) as u8,
+            (column & 0x0f) as u8,
+            0x10 | (column >> 4) as u8,
+        ])
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+
+    #[test]
+    fn test_init() {
+        let mut interface = RecordingInterface::default();
+        Ssd1306::new(OledController::Ssd1306, OledSize::Display128x32).init(&mut interface).unwrap();
+
+        let commands = interface.commands();
+        assert_eq!(Transfer::Reset, interface.transfers[0]);
+        assert_eq!(&[0xae, 0xd5, 0x80, 0xa8, 31], &commands[..5]);
+        assert!(commands.windows(2).any(|c| c == [0x8d, 0x14]));
+        assert!(commands.windows(2).any(|c| c == [0xda, 0x02]));
+        assert_eq!(Some(&0xaf), commands.last());
+
+        let mut interface = RecordingInterface::default();
+        Ssd1306::new(OledController::Sh1106, OledSize::Display128x64).init(&mut interface).unwrap();
+
+        let commands = interface.commands();
+        assert!(commands.windows(2).any(|c| c == [0xad, 0x8b]));
+        assert!(!commands.contains(&0x20));
+        assert!(commands.windows(2).any(|c| c == [0xda, 0x12]));
+    }
+
+    #[test]
+    fn test_flush() {
+        let mut image = VerticalEightPxUintEight::new(128, 32, EightDataClient::new(512)).unwrap();
+        image.fill((16, 8, 2, 9), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        let mut driver = Ssd1306::new(OledController::Ssd1306, OledSize::Display128x32);
+        let window = driver.flush(&mut interface, &image, (16, 8, 2, 9)).unwrap();
+
+        assert_eq!(Rectangle::new(16, 1, 2, 2), window);
+        assert_eq!(
+            vec![
+                Transfer::Command(0xb1), Transfer::Command(0x00), Transfer::Command(0x11),
+                Transfer::Data(vec![0b_1111_1111, 0b_1111_1111]),
+                Transfer::Command(0xb2), Transfer::Command(0x00), Transfer::Command(0x11),
+                Transfer::Data(vec![0b_0000_0001, 0b_0000_0001]),
+            ],
+            interface.transfers
+        );
+    }
+
+    #[test]
+    fn test_sh1106_offset() {
+        let mut image = VerticalEightPxUintEight::new(128, 32, EightDataClient::new(512)).unwrap();
+        image.fill((16, 8, 2, 9), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        let mut driver = Ssd1306::new(OledController::Sh1106, OledSize::Display128x32);
+        driver.flush(&mut interface, &image, (0, 0, 128, 32)).unwrap();
+
+        assert_eq!(
+            vec![
+                0xb0, 0x02, 0x10,
+                0xb1, 0x02, 0x10,
+                0xb2, 0x02, 0x10,
+                0xb3, 0x02, 0x10,
+            ],
+            interface.commands()
+        );
+        assert_eq!(image.as_vec(), interface.data().as_slice());
+    }
+}
@@ -0,0 +1,256 @@
+use crate::*;
+
+const WRITE_BLACK_WHITE_RAM: u8 = 0x24;
+const WRITE_RED_RAM: u8 = 0x26;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Refresh {
+    /// Refresh all pixels with flashing.
+    Full,
+    /// Refresh changed pixels from the previous image without flashing.
+    Partial,
+}
+
+/// RAM window of SSD1680. X is in bytes of 8 pixels, same as cells of `HorizontalEightPxUintEight`.
+struct Ssd1680Window {
+    ram: u8,
+}
+
+impl RamWindow for Ssd1680Window {
+    fn set_window<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        window: Rectangle,
+    ) -> Result<(), I::Error> {
+        let (x_end, y_end) = (window.x + window.width - 1, window.y + window.height - 1);
+
+        interface.send_command_with_data(0x44, &[window.x as u8, x_end as u8])?;
+        interface.send_command_with_data(
+            0x45,
+            &[
+                window.y as u8,
+                (window.y >> 8) as u8,
+                y_end as u8,
+                (y_end >> 8) as u8,
+            ],
+        )?;
+        interface.send_command_with_data(0x4e, &[window.x as u8])?;
+        interface.send_command_with_data(0x4f, &[window.y as u8, (window.y >> 8) as u8])?;
+        interface.send_command(self.ram)
+    }
+}
+
+/// SSD1680 and SSD1675 e-paper. ex: 2.13" 122 x 250, 2.9" 128 x 296
+///
+/// `Mono::One` is black. RAM has 0 as black, so bytes are inverted while sending.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Ssd1680 {
+    width: usize,
+    height: usize,
+}
+
+impl Ssd1680 {
+    /// Zero width or height is an error because RAM windows end at `size - 1`.
+    pub fn new(width: usize, height: usize) -> EightPxUintEightResult<Self> {
+        if width == 0 || height == 0 {
+            return Err(EightPxUintEightError::InvalidLengthData);
+        }
+
+        Ok(Self { width, height })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Reset and configure the controller.
+    pub fn init<I: DisplayInterface>(&mut self, interface: &mut I) -> Result<(), I::Error> {
+        let gates = self.height - 1;
+
+        interface.reset()?;
+        interface.wait_busy()?;
+        interface.send_command(0x12)?; // software reset
+        interface.wait_busy()?;
+
+        // driver output: gates and scan direction
+        interface.send_command_with_data(0x01, &[gates as u8, (gates >> 8) as u8, 0x00])?;
+        // data entry: X and Y increment
+        interface.send_command_with_data(0x11, &[0x03])?;
+        // border waveform
+        interface.send_command_with_data(0x3c, &[0x05])?;
+        // internal temperature sensor
+        interface.send_command_with_data(0x18, &[0x80])?;
+        interface.wait_busy()
+    }
+
+    /// Write the rectangle of the image into RAM. It is not shown until `refresh`.
+    pub fn write<I: DisplayInterface, D: EightData>(
+        &mut self,
+        interface: &mut I,
+        image: &HorizontalEightPxUintEight<D>,
+        xywh: impl ActAsXywh,
+    ) -> Result<Rectangle, I::Error> {
+        self.write_ram(interface, image, xywh, WRITE_BLACK_WHITE_RAM)
+    }
+
+    /// Write the rectangle of the image as the previous image that partial refresh compares with.
+    pub fn write_previous<I: DisplayInterface, D: EightData>(
+        &mut self,
+        interface: &mut I,
+        image: &HorizontalEightPxUintEight<D>,
+        xywh: impl ActAsXywh,
+    ) -> Result<Rectangle, I::Error> {
+        self.write_ram(interface, image, xywh, WRITE_RED_RAM)
+    }
+
+    fn write_ram<I: DisplayInterface, D: EightData>(
+        &mut self,
+        interface: &mut I,
+        image: &HorizontalEightPxUintEight<D>,
+        xywh: impl ActAsXywh,
+        ram: u8,
+    ) -> Result<Rectangle, I::Error> {
+        flush_by(image, xywh, &mut Ssd1680Window { ram }, interface, |byte| {
+            !byte
+        })
+    }
+
+    /// Show the image in RAM and wait for the end of refresh.
+    pub fn refresh<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        refresh: Refresh,
+    ) -> Result<(), I::Error> {
+        let sequence = match refresh {
+            Refresh::Full => 0xf7,
+            Refresh::Partial => 0xfc,
+        };
+
+        interface.send_command_with_data(0x22, &[sequence])?;
+        interface.send_command(0x20)?;
+        interface.wait_busy()
+    }
+
+    /// Write, refresh and keep the rectangle as the previous image for the next partial refresh.
+    pub fn update<I: DisplayInterface, D: EightData>(
+        &mut self,
+        interface: &mut I,
+        image: &HorizontalEightPxUintEight<D>,
+        xywh: impl ActAsXywh + Copy,
+        refresh: Refresh,
+    ) -> Result<Rectangle, I::Error> {
+        let window = self.write(interface, image, xywh)?;
+        self.refresh(interface, refresh)?;
+        self.write_previous(interface, image, xywh)?;
+
+        Ok(window)
+    }
+
+    /// Deep sleep. It needs a reset to wake up.
+    pub fn sleep<I: DisplayInterface>(&mut self, interface: &mut I) -> Result<(), I::Error> {
+        interface.send_command_with_data(0x10, &[0x01])
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+
+    #[test]
+    fn test_init() {
+        let mut interface = RecordingInterface::default();
+        Ssd1680::new(122, 250).unwrap().init(&mut interface).unwrap();
+
+        assert_eq!(
+            vec![
+                Transfer::Reset,
+                Transfer::WaitBusy,
+                Transfer::Command(0x12),
+                Transfer::WaitBusy,
+                Transfer::Command(0x01), Transfer::Data(vec![249, 0, 0]),
+                Transfer::Command(0x11), Transfer::Data(vec![0x03]),
+                Transfer::Command(0x3c), Transfer::Data(vec![0x05]),
+                Transfer::Command(0x18), Transfer::Data(vec![0x80]),
+                Transfer::WaitBusy,
+            ],
+            interface.transfers
+        );
+    }
+
+    #[test]
+    fn test_zero_size() {
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), Ssd1680::new(122, 0));
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), Ssd1680::new(0, 250));
+    }
+
+    #[test]
+    fn test_partial_update() {
+        let mut image = HorizontalEightPxUintEight::new(122, 250, EightDataClient::new(16 * 250)).unwrap();
+        image.fill((8, 240, 8, 2), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        let window = Ssd1680::new(122, 250)
+            .unwrap()
+            .update(&mut interface, &image, (10, 240, 4, 2), Refresh::Partial)
+            .unwrap();
+
+        assert_eq!(Rectangle::new(1, 240, 1, 2), window);
+
+        let ram = vec![
+            Transfer::Command(0x44), Transfer::Data(vec![1, 1]),
+            Transfer::Command(0x45), Transfer::Data(vec![240, 0, 241, 0]),
+            Transfer::Command(0x4e), Transfer::Data(vec![1]),
+            Transfer::Command(0x4f), Transfer::Data(vec![240, 0]),
+        ];
+        let expected = [
+            ram.clone(),
+            vec![
+                Transfer::Command(0x24),
+                Transfer::Data(vec![0b_0000_0000]),
+                Transfer::Data(vec![0b_0000_0000]),
+                Transfer::Command(0x22), Transfer::Data(vec![0xfc]),
+                Transfer::Command(0x20),
+                Transfer::WaitBusy,
+            ],
+            ram,
+            vec![
+                Transfer::Command(0x26),
+                Transfer::Data(vec![0b_0000_0000]),
+                Transfer::Data(vec![0b_0000_0000]),
+            ],
+        ].concat();
+
+        assert_eq!(expected, interface.transfers);
+    }
+
+    #[test]
+    fn test_full_refresh() {
+        let mut image = HorizontalEightPxUintEight::new(122, 250, EightDataClient::new(16 * 250)).unwrap();
+        image.fill((8, 240, 8, 2), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        let mut driver = Ssd1680::new(122, 250).unwrap();
+        driver.write(&mut interface, &image, (0, 0, 122, 250)).unwrap();
+
+        let data = interface.data();
+        // window commands and 16 * 250 bytes
+        assert_eq!(2 + 4 + 1 + 2 + 16 * 250, data.len());
+        assert_eq!(Some(&0xff), data.last());
+        assert_eq!(vec![0x44, 0x45, 0x4e, 0x4f, 0x24], interface.commands());
+
+        interface.clear();
+        driver.refresh(&mut interface, Refresh::Full).unwrap();
+        assert_eq!(Transfer::Data(vec![0xf7]), interface.transfers[1]);
+
+        interface.clear();
+        driver.sleep(&mut interface).unwrap();
+        assert_eq!(vec![Transfer::Command(0x10), Transfer::Data(vec![0x01])], interface.transfers);
+    }
+}
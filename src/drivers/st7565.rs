@@ -0,0 +1,184 @@
+use crate::*;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum St7565Bias {
+    OneNinth,
+    OneSeventh,
+}
+
+/// ST7565 and ST7567 LCD, 128 x 64. RAM is written by pages, same as `VerticalEightPxUintEight`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct St7565 {
+    bias: St7565Bias,
+    contrast: u8,
+    regulation_ratio: u8,
+    column_offset: usize,
+}
+
+impl Default for St7565 {
+    fn default() -> Self {
+        Self {
+            bias: St7565Bias::OneNinth,
+            contrast: 0x20,
+            regulation_ratio: 0x05,
+            column_offset: 0,
+        }
+    }
+}
+
+impl St7565 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Some modules show RAM from column 4 of 132 columns.
+    pub fn with_column_offset(self, column_offset: usize) -> Self {
+        Self {
+            column_offset,
+            ..self
+        }
+    }
+
+    /// Regulation resistor ratio `0..=7` of the voltage regulator.
+    pub fn with_regulation_ratio(self, regulation_ratio: u8) -> Self {
+        Self {
+            regulation_ratio: regulation_ratio & 0x07,
+            ..self
+        }
+    }
+
+    pub fn bias(&self) -> St7565Bias {
+        self.bias
+    }
+
+    pub fn contrast(&self) -> u8 {
+        self.contrast
+    }
+
+    fn bias_command(&self) -> u8 {
+        match self.bias {
+            St7565Bias::OneNinth => 0xa2,
+            St7565Bias::OneSeventh => 0xa3,
+        }
+    }
+
+    /// Configure the controller and turn the display on.
+    #[rustfmt::skip]
+    pub fn init<I: DisplayInterface>(&mut self, interface: &mut I) -> Result<(), I::Error> {
+        interface.reset()?;
+        interface.send_commands(&[
+            0xe2, // software reset
+            self.bias_command(),
+            0xa0, // segment direction
+            0xc8, // COM direction
+            0x20 | self.regulation_ratio,
+            0x81, self.contrast, // electronic volume
+            0x2f, // booster, regulator and follower on
+            0x40, // start line
+            0xa6, // not inverted
+            0xa4, // display RAM
+            0xaf, // display on
+        ])
+    }
+
+    /// Electronic volume `0..=63`.
+    pub fn set_contrast<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        contrast: u8,
+    ) -> Result<(), I::Error> {
+        self.contrast = contrast & 0x3f;
+        interface.send_commands(&[0x81, self.contrast])
+    }
+
+    pub fn set_bias<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        bias: St7565Bias,
+    ) -> Result<(), I::Error> {
+        self.bias = bias;
+        interface.send_command(self.bias_command())
+    }
+
+    /// Send the pages that have the rectangle of the image.
+    pub fn flush<I: DisplayInterface, D: EightData>(
+        &mut self,
+        interface: &mut I,
+        image: &VerticalEightPxUintEight<D>,
+        xywh: impl ActAsXywh,
+    ) -> Result<Rectangle, I::Error> {
+        flush(image, xywh, self, interface)
+    }
+}
+
+impl RamWindow for St7565 {
+    fn set_window<I: DisplayInterface>(
+        &mut self,
+        _interface: &mut I,
+        _window: Rectangle,
+    ) -> Result<(), I::Error> {
+        Ok(())
+    }
+
+    fn start_row<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        window: Rectangle,
+        row: usize,
+    ) -> Result<(), I::Error> {
+        let column = window.x + self.column_offset;
+
+        interface.send_commands(&[
+            0xb0 | (window.y + row) as u8,
+            0x10 | (column >> 4) as u8,
+            (column & 0x0f) as u8,
+        ])
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+
+    #[test]
+    fn test_init() {
+        let mut interface = RecordingInterface::default();
+        let mut lcd = St7565::new().with_regulation_ratio(3);
+        lcd.init(&mut interface).unwrap();
+
+        assert_eq!(
+            vec![0xe2, 0xa2, 0xa0, 0xc8, 0x23, 0x81, 0x20, 0x2f, 0x40, 0xa6, 0xa4, 0xaf],
+            interface.commands()
+        );
+
+        interface.clear();
+        lcd.set_contrast(&mut interface, 0x7f).unwrap();
+        lcd.set_bias(&mut interface, St7565Bias::OneSeventh).unwrap();
+        assert_eq!(vec![0x81, 0x3f, 0xa3], interface.commands());
+        assert_eq!((0x3f, St7565Bias::OneSeventh), (lcd.contrast(), lcd.bias()));
+    }
+
+    #[test]
+    fn test_flush() {
+        let mut image = VerticalEightPxUintEight::new(128, 64, EightDataClient::new(1024)).unwrap();
+        image.fill((20, 0, 2, 4), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        let window = St7565::new()
+            .with_column_offset(4)
+            .flush(&mut interface, &image, (20, 0, 2, 4))
+            .unwrap();
+
+        assert_eq!(Rectangle::new(20, 0, 2, 1), window);
+        assert_eq!(
+            vec![
+                Transfer::Command(0xb0), Transfer::Command(0x11), Transfer::Command(0x08),
+                Transfer::Data(vec![0b_0000_1111; 2]),
+            ],
+            interface.transfers
+        );
+    }
+}
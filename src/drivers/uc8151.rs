@@ -0,0 +1,326 @@
+use crate::*;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Uc8151Color {
+    BlackWhite,
+    /// Tri-color panels that have the red plane.
+    BlackWhiteRed,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Plane {
+    Black,
+    /// Only tri-color panels.
+    Red,
+}
+
+/// Partial window of UC8151. X is in pixels, but it must be multiples of 8.
+struct Uc8151Window {
+    command: u8,
+}
+
+fn partial_window<I: DisplayInterface>(
+    interface: &mut I,
+    window: Rectangle,
+) -> Result<(), I::Error> {
+    let (x_start, x_end) = (window.x * 8, (window.x + window.width) * 8 - 1);
+    let (y_start, y_end) = (window.y, window.y + window.height - 1);
+
+    interface.send_command(0x91)?; // partial in
+    interface.send_command_with_data(
+        0x90,
+        &[
+            x_start as u8,
+            x_end as u8,
+            (y_start >> 8) as u8,
+            y_start as u8,
+            (y_end >> 8) as u8,
+            y_end as u8,
+            0x01, // scan only in the window
+        ],
+    )
+}
+
+impl RamWindow for Uc8151Window {
+    fn set_window<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        window: Rectangle,
+    ) -> Result<(), I::Error> {
+        partial_window(interface, window)?;
+        interface.send_command(self.command)
+    }
+}
+
+/// UC8151 and IL0373 e-paper with `HorizontalEightPxUintEight`. ex: 2.9" 128 x 296, 2.13" 104 x 212
+///
+/// `Mono::One` is black (or red on the red plane). RAM has 0 as the color, so bytes are inverted while sending.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Uc8151 {
+    width: usize,
+    height: usize,
+    color: Uc8151Color,
+}
+
+impl Uc8151 {
+    pub fn new(width: usize, height: usize, color: Uc8151Color) -> Self {
+        Self {
+            width,
+            height,
+            color,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn color(&self) -> Uc8151Color {
+        self.color
+    }
+
+    /// Reset, power on and configure the controller.
+    pub fn init<I: DisplayInterface>(&mut self, interface: &mut I) -> Result<(), I::Error> {
+        let (panel, interval) = match self.color {
+            Uc8151Color::BlackWhite => (0xdf, 0x97),
+            Uc8151Color::BlackWhiteRed => (0xcf, 0x37),
+        };
+
+        interface.reset()?;
+        // booster soft start
+        interface.send_command_with_data(0x06, &[0x17, 0x17, 0x17])?;
+        interface.send_command(0x04)?; // power on
+        interface.wait_busy()?;
+
+        interface.send_command_with_data(0x00, &[panel])?;
+        interface.send_command_with_data(0x50, &[interval])?;
+        // PLL
+        interface.send_command_with_data(0x30, &[0x29])?;
+        // VCOM DC
+        interface.send_command_with_data(0x82, &[0x0a])?;
+        // resolution
+        interface.send_command_with_data(
+            0x61,
+            &[
+                self.width as u8,
+                (self.height >> 8) as u8,
+                self.height as u8,
+            ],
+        )
+    }
+
+    fn data_command(&self, plane: Plane) -> Option<u8> {
+        match (self.color, plane) {
+            // black and white panels show DTM2, DTM1 is the previous image
+            (Uc8151Color::BlackWhite, Plane::Black) => Some(0x13),
+            (Uc8151Color::BlackWhite, Plane::Red) => None,
+            (Uc8151Color::BlackWhiteRed, Plane::Black) => Some(0x10),
+            (Uc8151Color::BlackWhiteRed, Plane::Red) => Some(0x13),
+        }
+    }
+
+    /// Write the rectangle of the image into the plane through the partial window. It is not shown until refresh.
+    ///
+    /// Black and white panels have no red plane, so nothing is sent for `Plane::Red` and an empty rectangle is returned.
+    pub fn write<I: DisplayInterface, D: EightData>(
+        &mut self,
+        interface: &mut I,
+        plane: Plane,
+        image: &HorizontalEightPxUintEight<D>,
+        xywh: impl ActAsXywh,
+    ) -> Result<Rectangle, I::Error> {
+        match self.data_command(plane) {
+            Some(command) => self.write_ram(interface, image, xywh, command),
+            None => Ok(Rectangle::new(0, 0, 0, 0)),
+        }
+    }
+
+    /// Write the rectangle of the image as the previous image (DTM1) that partial refresh compares with.
+    ///
+    /// Tri-color panels use DTM1 as the black plane, so nothing is sent for them and an empty rectangle is returned.
+    pub fn write_previous<I: DisplayInterface, D: EightData>(
+        &mut self,
+        interface: &mut I,
+        image: &HorizontalEightPxUintEight<D>,
+        xywh: impl ActAsXywh,
+    ) -> Result<Rectangle, I::Error> {
+        match self.color {
+            Uc8151Color::BlackWhite => self.write_ram(interface, image, xywh, 0x10),
+            Uc8151Color::BlackWhiteRed => Ok(Rectangle::new(0, 0, 0, 0)),
+        }
+    }
+
+    fn write_ram<I: DisplayInterface, D: EightData>(
+        &mut self,
+        interface: &mut I,
+        image: &HorizontalEightPxUintEight<D>,
+        xywh: impl ActAsXywh,
+        command: u8,
+    ) -> Result<Rectangle, I::Error> {
+        let mut window = Uc8151Window { command };
+        let window = flush_by(image, xywh, &mut window, interface, |byte| !byte)?;
+
+        // nothing is sent for a rectangle outside of the image, so no partial in to close
+        if window.width == 0 || window.height == 0 {
+            return Ok(window);
+        }
+        interface.send_command(0x92)?; // partial out
+
+        Ok(window)
+    }
+
+    /// Refresh the whole display.
+    pub fn refresh<I: DisplayInterface>(&mut self, interface: &mut I) -> Result<(), I::Error> {
+        interface.send_command(0x12)?;
+        interface.wait_busy()
+    }
+
+    /// Refresh only the window that `write` returns. An empty window refreshes nothing.
+    pub fn refresh_window<I: DisplayInterface>(
+        &mut self,
+        interface: &mut I,
+        window: Rectangle,
+    ) -> Result<(), I::Error> {
+        if window.width == 0 || window.height == 0 {
+            return Ok(());
+        }
+
+        partial_window(interface, window)?;
+        interface.send_command(0x12)?;
+        interface.wait_busy()?;
+        interface.send_command(0x92)
+    }
+
+    /// Power off and deep sleep. It needs a reset to wake up.
+    pub fn sleep<I: DisplayInterface>(&mut self, interface: &mut I) -> Result<(), I::Error> {
+        interface.send_command(0x02)?; // power off
+        interface.wait_busy()?;
+        interface.send_command_with_data(0x07, &[0xa5])
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+
+    #[test]
+    fn test_init() {
+        let mut interface = RecordingInterface::default();
+        Uc8151::new(128, 296, Uc8151Color::BlackWhiteRed).init(&mut interface).unwrap();
+
+        assert_eq!(vec![0x06, 0x04, 0x00, 0x50, 0x30, 0x82, 0x61], interface.commands());
+        assert_eq!(Transfer::Data(vec![0xcf]), interface.transfers[6]);
+        assert_eq!(Some(&Transfer::Data(vec![128, 1, 40])), interface.transfers.last());
+
+        let mut interface = RecordingInterface::default();
+        Uc8151::new(128, 296, Uc8151Color::BlackWhite).init(&mut interface).unwrap();
+        assert_eq!(Transfer::Data(vec![0xdf]), interface.transfers[6]);
+    }
+
+    #[test]
+    fn test_partial() {
+        let mut image = HorizontalEightPxUintEight::new(128, 296, EightDataClient::new(16 * 296)).unwrap();
+        image.fill((16, 258, 16, 2), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        let mut driver = Uc8151::new(128, 296, Uc8151Color::BlackWhiteRed);
+        let window = driver.write(&mut interface, Plane::Red, &image, (16, 258, 16, 2)).unwrap();
+
+        assert_eq!(Rectangle::new(2, 258, 2, 2), window);
+
+        let partial = vec![
+            Transfer::Command(0x91),
+            Transfer::Command(0x90), Transfer::Data(vec![16, 31, 1, 2, 1, 3, 0x01]),
+        ];
+        assert_eq!(
+            [
+                partial.clone(),
+                vec![
+                    Transfer::Command(0x13),
+                    Transfer::Data(vec![0b_0000_0000, 0b_0000_0000]),
+                    Transfer::Data(vec![0b_0000_0000, 0b_0000_0000]),
+                    Transfer::Command(0x92),
+                ],
+            ].concat(),
+            interface.transfers
+        );
+
+        interface.clear();
+        driver.refresh_window(&mut interface, window).unwrap();
+        assert_eq!(
+            [
+                partial,
+                vec![Transfer::Command(0x12), Transfer::WaitBusy, Transfer::Command(0x92)],
+            ].concat(),
+            interface.transfers
+        );
+    }
+
+    #[test]
+    fn test_planes() {
+        let mut image = HorizontalEightPxUintEight::new(128, 296, EightDataClient::new(16 * 296)).unwrap();
+        image.fill((16, 258, 16, 2), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        let mut driver = Uc8151::new(128, 296, Uc8151Color::BlackWhiteRed);
+        driver.write(&mut interface, Plane::Black, &image, (0, 0, 128, 296)).unwrap();
+        driver.refresh(&mut interface).unwrap();
+
+        assert_eq!(vec![0x91, 0x90, 0x10, 0x92, 0x12], interface.commands());
+        // window and 16 * 296 bytes
+        assert_eq!(7 + 16 * 296, interface.data().len());
+
+        let mut interface = RecordingInterface::default();
+        let mut driver = Uc8151::new(128, 296, Uc8151Color::BlackWhite);
+        driver.write(&mut interface, Plane::Black, &image, (0, 0, 8, 8)).unwrap();
+        driver.sleep(&mut interface).unwrap();
+
+        assert_eq!(vec![0x91, 0x90, 0x13, 0x92, 0x02, 0x07], interface.commands());
+    }
+
+    #[test]
+    fn test_black_white_planes() {
+        let mut image = HorizontalEightPxUintEight::new(128, 296, EightDataClient::new(16 * 296)).unwrap();
+        image.fill((16, 258, 16, 2), Mono::One);
+
+        let mut interface = RecordingInterface::default();
+        let mut driver = Uc8151::new(128, 296, Uc8151Color::BlackWhite);
+
+        // no red plane
+        assert_eq!(Ok(Rectangle::new(0, 0, 0, 0)), driver.write(&mut interface, Plane::Red, &image, (0, 0, 8, 8)));
+        assert!(interface.transfers.is_empty());
+
+        driver.write(&mut interface, Plane::Black, &image, (16, 258, 16, 2)).unwrap();
+        driver.write_previous(&mut interface, &image, (16, 258, 16, 2)).unwrap();
+        assert_eq!(vec![0x91, 0x90, 0x13, 0x92, 0x91, 0x90, 0x10, 0x92], interface.commands());
+
+        let mut interface = RecordingInterface::default();
+        let mut driver = Uc8151::new(128, 296, Uc8151Color::BlackWhiteRed);
+        driver.write_previous(&mut interface, &image, (0, 0, 8, 8)).unwrap();
+        assert!(interface.transfers.is_empty());
+    }
+
+    #[test]
+    fn test_empty_window() {
+        let image = HorizontalEightPxUintEight::new(128, 296, EightDataClient::new(16 * 296)).unwrap();
+
+        let mut interface = RecordingInterface::default();
+        let mut driver = Uc8151::new(128, 296, Uc8151Color::BlackWhite);
+
+        let window = driver.write(&mut interface, Plane::Red, &image, (0, 0, 8, 8)).unwrap();
+        driver.refresh_window(&mut interface, window).unwrap();
+
+        let window = driver.write(&mut interface, Plane::Black, &image, (128, 0, 8, 8)).unwrap();
+        assert_eq!(Rectangle::new(0, 0, 0, 0), window);
+        driver.refresh_window(&mut interface, window).unwrap();
+
+        assert!(interface.transfers.is_empty());
+    }
+}
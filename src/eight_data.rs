@@ -3,4 +3,63 @@ pub trait EightData {
     fn core_mut(&mut self) -> &mut [u8];
     fn as_vev(&self) -> &[u8];
     fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Fixed size data that needs no allocation. It can be a constant.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ArrayEightData<const N: usize> {
+    core: [u8; N],
+}
+
+impl<const N: usize> ArrayEightData<N> {
+    pub const fn new(core: [u8; N]) -> Self {
+        Self { core }
+    }
+}
+
+impl<const N: usize> Default for ArrayEightData<N> {
+    fn default() -> Self {
+        Self::new([0; N])
+    }
+}
+
+impl<const N: usize> EightData for ArrayEightData<N> {
+    fn core(&self) -> &[u8] {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut [u8] {
+        &mut self.core
+    }
+
+    fn as_vev(&self) -> &[u8] {
+        &self.core
+    }
+
+    fn len(&self) -> usize {
+        N
+    }
+}
+
+/// Borrowed data. It makes an image on a buffer without copying.
+impl EightData for &mut [u8] {
+    fn core(&self) -> &[u8] {
+        self
+    }
+
+    fn core_mut(&mut self) -> &mut [u8] {
+        self
+    }
+
+    fn as_vev(&self) -> &[u8] {
+        self
+    }
+
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
 }
@@ -4,6 +4,7 @@ use core::cmp::min;
 pub trait EightPxUintEight {
     type EightData: EightData;
 
+    fn layout(&self) -> Layout;
     fn width(&self) -> usize;
     fn height(&self) -> usize;
     fn eight_length(&self) -> usize;
@@ -11,12 +12,32 @@ pub trait EightPxUintEight {
     fn eight_data_mut(&mut self) -> &mut Self::EightData;
 
     fn draw(&mut self, x: usize, y: usize, color: Mono);
+    fn pixel(&self, x: usize, y: usize) -> Mono;
     fn compute_part(&self, xywh: impl ActAsXywh) -> Part;
 
     fn update(
         &mut self,
         xywh: impl ActAsXywh,
         src: &[impl ActAsMono],
+    ) -> EightPxUintEightResult<()> {
+        self.update_by(xywh, src, |n| n.act_as())
+    }
+
+    /// Update pixels with a threshold. ex: 8-bit grayscale, RGB
+    fn update_threshold(
+        &mut self,
+        xywh: impl ActAsXywh,
+        src: &[impl ActAsLuma],
+        threshold: Threshold,
+    ) -> EightPxUintEightResult<()> {
+        self.update_by(xywh, src, |n| threshold.mono(n))
+    }
+
+    fn update_by<T>(
+        &mut self,
+        xywh: impl ActAsXywh,
+        src: &[T],
+        act_as: impl Fn(&T) -> Mono,
     ) -> EightPxUintEightResult<()> {
         let (x, y, width, height) = xywh.xywh();
 
@@ -29,10 +50,10 @@ pub trait EightPxUintEight {
         for step_y in 0..min(height, self.height() - y) {
             for step_x in 0..min(width, self.width() - x) {
                 let index = width * step_y + step_x;
-                let color = match src.get(width * step_y + step_x) {
+                let color = act_as(match src.get(width * step_y + step_x) {
                     None => return Err(EightPxUintEightError::Overflow((src.len(), index))),
                     Some(n) => n,
-                }.act_as();
+                });
                 let data_x = x + step_x;
                 let data_y = y + step_y;
 
@@ -43,8 +64,52 @@ pub trait EightPxUintEight {
         Ok(())
     }
 
+    fn fill(&mut self, xywh: impl ActAsXywh, color: Mono) {
+        let (x, y, width, height) = xywh.xywh();
+
+        // avoid unsigned subtract overflow
+        if x > self.width() || y > self.height() {
+            return;
+        }
+
+        // discard pixels that overflow
+        for step_y in 0..min(height, self.height() - y) {
+            for step_x in 0..min(width, self.width() - x) {
+                self.draw(x + step_x, y + step_y, color);
+            }
+        }
+    }
+
+    /// Copy a rectangle of `src` to (x, y). The layout of `src` may differ.
+    fn blit(&mut self, x: usize, y: usize, src: &impl EightPxUintEight, xywh: impl ActAsXywh) {
+        let (src_x, src_y, width, height) = xywh.xywh();
+
+        // avoid unsigned subtract overflow
+        if x > self.width() || y > self.height() || src_x > src.width() || src_y > src.height() {
+            return;
+        }
+
+        // discard pixels that overflow
+        let width = min(min(width, src.width() - src_x), self.width() - x);
+        let height = min(min(height, src.height() - src_y), self.height() - y);
+
+        for step_y in 0..height {
+            for step_x in 0..width {
+                let color = src.pixel(src_x + step_x, src_y + step_y);
+                self.draw(x + step_x, y + step_y, color);
+            }
+        }
+    }
+
+    fn preview(&self, style: PreviewStyle) -> Preview<'_, Self>
+    where
+        Self: Sized,
+    {
+        Preview::new(self, style)
+    }
+
     fn as_vec(&self) -> &[u8] {
-        &self.eight_data().as_vev()
+        self.eight_data().as_vev()
     }
 
     /// Return rectangle as 1 cell has 8 pixels.
@@ -1,6 +1,7 @@
 #[derive(Debug, Eq, PartialEq)]
 pub enum EightPxUintEightError {
     InvalidLengthData,
+    InvalidFormat,
     Overflow((usize,usize)),
 }
 
@@ -0,0 +1,270 @@
+use crate::*;
+use core::cmp::min;
+
+const ESC: u8 = 0x1b;
+const GS: u8 = 0x1d;
+const LF: u8 = 0x0a;
+
+/// Line spacing of column bands. 8-dot modes print a dot 3 dots tall, so a band of any mode is 24 dots.
+const COLUMN_LINE_SPACING: u8 = 24;
+
+/// Dots per column of `ESC *`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ColumnDensity {
+    EightSingle,
+    EightDouble,
+    TwentyFourSingle,
+    TwentyFourDouble,
+}
+
+impl ColumnDensity {
+    fn mode(&self) -> u8 {
+        match self {
+            ColumnDensity::EightSingle => 0,
+            ColumnDensity::EightDouble => 1,
+            ColumnDensity::TwentyFourSingle => 32,
+            ColumnDensity::TwentyFourDouble => 33,
+        }
+    }
+
+    /// Pages of `VerticalEightPxUintEight` in a band.
+    fn pages(&self) -> usize {
+        match self {
+            ColumnDensity::EightSingle | ColumnDensity::EightDouble => 1,
+            ColumnDensity::TwentyFourSingle | ColumnDensity::TwentyFourDouble => 3,
+        }
+    }
+}
+
+/// Sizes of commands are 16 bits. Larger ones are an error instead of being truncated.
+fn u16_le(n: usize) -> EightPxUintEightResult<[u8; 2]> {
+    match n > u16::MAX as usize {
+        true => Err(EightPxUintEightError::Overflow((u16::MAX as usize, n))),
+        false => Ok([n as u8, (n >> 8) as u8]),
+    }
+}
+
+/// ESC/POS images for thermal printers. ex: 384 dots of 58mm paper, 576 dots of 80mm paper
+///
+/// `Mono::One` is black, same as the printers. Tall images are split into bands of `band_height` lines
+/// so that a command does not overflow the buffer of the printer.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct EscPos {
+    band_height: usize,
+}
+
+impl Default for EscPos {
+    fn default() -> Self {
+        Self { band_height: 256 }
+    }
+}
+
+impl EscPos {
+    pub fn new(band_height: usize) -> Self {
+        Self {
+            band_height: band_height.max(1),
+        }
+    }
+
+    pub fn band_height(&self) -> usize {
+        self.band_height
+    }
+
+    /// Length of `GS v 0` bands of the size.
+    pub fn raster_length(&self, width: usize, height: usize) -> usize {
+        let bands = height.div_ceil(self.band_height);
+        bands * 8 + compute_eight_length(width) * height
+    }
+
+    /// Encode the image as `GS v 0` bands. Rows of `HorizontalEightPxUintEight` are sent as is.
+    ///
+    /// Return the length written.
+    pub fn encode_raster<D: EightData>(
+        &self,
+        image: &HorizontalEightPxUintEight<D>,
+        dst: &mut [u8],
+    ) -> EightPxUintEightResult<usize> {
+        let length = image.eight_length();
+        let src = image.as_vec();
+        let mut output = Output::new(dst);
+
+        let mut y = 0;
+        while y < image.height() {
+            let lines = min(self.band_height, image.height() - y);
+            let [x_l, x_h] = u16_le(length)?;
+            let [y_l, y_h] = u16_le(lines)?;
+
+            output.put(&[GS, b'v', b'0', 0, x_l, x_h, y_l, y_h])?;
+            output.put(&src[length * y..length * (y + lines)])?;
+            y += lines;
+        }
+
+        Ok(output.written())
+    }
+
+    /// Length of `ESC *` bands of the size.
+    pub fn columns_length(&self, width: usize, height: usize, density: ColumnDensity) -> usize {
+        let pages = density.pages();
+        let bands = compute_eight_length(height).div_ceil(pages);
+        3 + bands * (5 + width * pages + 1) + 2
+    }
+
+    /// Encode the image as `ESC *` bands of 8 or 24 dots with line spacing for them.
+    ///
+    /// A band is always the dots of the density, so `band_height` smaller than it is an error.
+    /// `ESC *` has the top dot at MSB, so bits of `VerticalEightPxUintEight` are reversed.
+    /// Return the length written.
+    pub fn encode_columns<D: EightData>(
+        &self,
+        image: &VerticalEightPxUintEight<D>,
+        density: ColumnDensity,
+        dst: &mut [u8],
+    ) -> EightPxUintEightResult<usize> {
+        let width = image.width();
+        let page_length = image.eight_length();
+        let pages = density.pages();
+        let src = image.as_vec();
+
+        let dots = pages * 8;
+        if dots > self.band_height {
+            return Err(EightPxUintEightError::Overflow((self.band_height, dots)));
+        }
+        let [n_l, n_h] = u16_le(width)?;
+
+        let mut output = Output::new(dst);
+        output.put(&[ESC, b'3', COLUMN_LINE_SPACING])?;
+
+        for band in (0..page_length).step_by(pages) {
+            output.put(&[ESC, b'*', density.mode(), n_l, n_h])?;
+
+            for x in 0..width {
+                for page in band..band + pages {
+                    let byte = match page < page_length {
+                        true => src[width * page + x].reverse_bits(),
+                        false => 0,
+                    };
+                    output.put(&[byte])?;
+                }
+            }
+
+            output.put(&[LF])?;
+        }
+
+        // default line spacing
+        output.put(&[ESC, b'2'])
+    }
+
+    #[cfg(feature = "std")]
+    pub fn raster_to_vec<D: EightData>(
+        &self,
+        image: &HorizontalEightPxUintEight<D>,
+    ) -> EightPxUintEightResult<Vec<u8>> {
+        let mut dst = vec![0; self.raster_length(image.width(), image.height())];
+        self.encode_raster(image, &mut dst)?;
+
+        Ok(dst)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn columns_to_vec<D: EightData>(
+        &self,
+        image: &VerticalEightPxUintEight<D>,
+        density: ColumnDensity,
+    ) -> EightPxUintEightResult<Vec<u8>> {
+        let mut dst = vec![0; self.columns_length(image.width(), image.height(), density)];
+        self.encode_columns(image, density, &mut dst)?;
+
+        Ok(dst)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+
+    #[test]
+    fn test_raster() {
+        let mut image = HorizontalEightPxUintEight::new(10, 5, EightDataClient::new(10)).unwrap();
+        image.fill((0, 0, 1, 5), Mono::One);
+        image.fill((9, 4, 1, 1), Mono::One);
+
+        let printer = EscPos::new(2);
+        let vec = printer.raster_to_vec(&image).unwrap();
+
+        assert_eq!(printer.raster_length(10, 5), vec.len());
+        assert_eq!(
+            vec![
+                0x1d, b'v', b'0', 0, 2, 0, 2, 0,
+                0b_1000_0000, 0, 0b_1000_0000, 0,
+                0x1d, b'v', b'0', 0, 2, 0, 2, 0,
+                0b_1000_0000, 0, 0b_1000_0000, 0,
+                0x1d, b'v', b'0', 0, 2, 0, 1, 0,
+                0b_1000_0000, 0b_0100_0000,
+            ],
+            vec
+        );
+
+        assert_eq!(
+            Err(EightPxUintEightError::Overflow((20, 24))),
+            printer.encode_raster(&image, &mut [0; 20])
+        );
+    }
+
+    #[test]
+    fn test_columns() {
+        let mut image = VerticalEightPxUintEight::new(2, 10, EightDataClient::new(4)).unwrap();
+        image.fill((0, 0, 1, 1), Mono::One);
+        image.fill((1, 9, 1, 1), Mono::One);
+
+        let printer = EscPos::default();
+        assert_eq!(
+            vec![
+                0x1b, b'3', 24,
+                0x1b, b'*', 0, 2, 0, 0b_1000_0000, 0b_0000_0000, 0x0a,
+                0x1b, b'*', 0, 2, 0, 0b_0000_0000, 0b_0100_0000, 0x0a,
+                0x1b, b'2',
+            ],
+            printer.columns_to_vec(&image, ColumnDensity::EightSingle).unwrap()
+        );
+
+        let vec = printer.columns_to_vec(&image, ColumnDensity::TwentyFourDouble).unwrap();
+        assert_eq!(printer.columns_length(2, 10, ColumnDensity::TwentyFourDouble), vec.len());
+        assert_eq!(
+            vec![
+                0x1b, b'3', 24,
+                0x1b, b'*', 33, 2, 0,
+                0b_1000_0000, 0b_0000_0000, 0,
+                0b_0000_0000, 0b_0100_0000, 0,
+                0x0a,
+                0x1b, b'2',
+            ],
+            vec
+        );
+    }
+
+    #[test]
+    fn test_oversized() {
+        let image = VerticalEightPxUintEight::new(2, 10, EightDataClient::new(4)).unwrap();
+        assert_eq!(
+            Err(EightPxUintEightError::Overflow((16, 24))),
+            EscPos::new(16).columns_to_vec(&image, ColumnDensity::TwentyFourSingle)
+        );
+        assert!(EscPos::new(16).columns_to_vec(&image, ColumnDensity::EightSingle).is_ok());
+
+        let image = VerticalEightPxUintEight::new(65536, 1, EightDataClient::new(65536)).unwrap();
+        assert_eq!(
+            Err(EightPxUintEightError::Overflow((65535, 65536))),
+            EscPos::default().columns_to_vec(&image, ColumnDensity::EightSingle)
+        );
+
+        let image = HorizontalEightPxUintEight::new(8, 65536, EightDataClient::new(65536)).unwrap();
+        assert_eq!(
+            Err(EightPxUintEightError::Overflow((65535, 65536))),
+            EscPos::new(70000).raster_to_vec(&image)
+        );
+        assert!(EscPos::new(65535).raster_to_vec(&image).is_ok());
+    }
+}
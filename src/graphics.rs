@@ -0,0 +1,343 @@
+use crate::*;
+use core::convert::Infallible;
+use embedded_graphics_core::pixelcolor::BinaryColor;
+use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives;
+
+impl From<BinaryColor> for Mono {
+    fn from(color: BinaryColor) -> Self {
+        match color {
+            BinaryColor::On => Mono::One,
+            BinaryColor::Off => Mono::Zero,
+        }
+    }
+}
+
+impl From<Mono> for BinaryColor {
+    fn from(color: Mono) -> Self {
+        match color {
+            Mono::One => BinaryColor::On,
+            Mono::Zero => BinaryColor::Off,
+        }
+    }
+}
+
+impl ActAsMono for BinaryColor {
+    fn act_as(&self) -> Mono {
+        (*self).into()
+    }
+}
+
+/// Bits from `start` to `end` with LSB as 0.
+fn span_mask(start: usize, end: usize) -> u8 {
+    ((0xff_u16 << start) & !(0xff_u16 << end)) as u8
+}
+
+fn write_mask(byte: &mut u8, mask: u8, color: BinaryColor) {
+    match color {
+        BinaryColor::On => *byte |= mask,
+        BinaryColor::Off => *byte &= !mask,
+    }
+}
+
+/// Clip the area and return x, y, width and height of pixels in the image.
+fn clip(
+    image: &impl EightPxUintEight,
+    area: &primitives::Rectangle,
+) -> Option<(usize, usize, usize, usize)> {
+    let size = Size::new(image.width() as u32, image.height() as u32);
+    let area = area.intersection(&primitives::Rectangle::new(Point::zero(), size));
+
+    match area.is_zero_sized() {
+        true => None,
+        false => Some((
+            area.top_left.x as usize,
+            area.top_left.y as usize,
+            area.size.width as usize,
+            area.size.height as usize,
+        )),
+    }
+}
+
+/// Write colors of the area in row-major order. Bits for the same byte are gathered and written at once.
+///
+/// `locate` returns the index of the byte and the bit of a pixel.
+fn fill_contiguous_by<Image: EightPxUintEight>(
+    image: &mut Image,
+    area: &primitives::Rectangle,
+    colors: impl IntoIterator<Item = BinaryColor>,
+    locate: impl Fn(usize, usize) -> (usize, u8),
+) {
+    let (width, height) = (image.width() as i32, image.height() as i32);
+    let data = image.eight_data_mut().core_mut();
+    let mut colors = colors.into_iter();
+    // index, mask and bits of the byte being written
+    let mut pending: Option<(usize, u8, u8)> = None;
+
+    for y in area.rows() {
+        for x in area.columns() {
+            let color = match colors.next() {
+                Some(color) => color,
+                None => break,
+            };
+            if x < 0 || y < 0 || x >= width || y >= height {
+                continue;
+            }
+
+            let (index, bit) = locate(x as usize, y as usize);
+            let (mask, bits) = match pending {
+                Some((i, mask, bits)) if i == index => (mask, bits),
+                Some((i, mask, bits)) => {
+                    data[i] = data[i] & !mask | bits;
+                    (0, 0)
+                }
+                None => (0, 0),
+            };
+            let bits = match color {
+                BinaryColor::On => bits | bit,
+                BinaryColor::Off => bits,
+            };
+            pending = Some((index, mask | bit, bits));
+        }
+    }
+
+    if let Some((i, mask, bits)) = pending {
+        data[i] = data[i] & !mask | bits;
+    }
+}
+
+impl<D: EightData> OriginDimensions for HorizontalEightPxUintEight<D> {
+    fn size(&self) -> Size {
+        Size::new(self.width() as u32, self.height() as u32)
+    }
+}
+
+impl<D: EightData> DrawTarget for HorizontalEightPxUintEight<D> {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (width, height) = (self.width() as i32, self.height() as i32);
+
+        for Pixel(point, color) in pixels {
+            if (0..width).contains(&point.x) && (0..height).contains(&point.y) {
+                self.draw(point.x as usize, point.y as usize, color.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(
+        &mut self,
+        area: &primitives::Rectangle,
+        colors: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let length = self.eight_length();
+        fill_contiguous_by(self, area, colors, |x, y| {
+            (length * y + (x >> 3), 0b_1000_0000 >> (x % 8))
+        });
+
+        Ok(())
+    }
+
+    fn fill_solid(
+        &mut self,
+        area: &primitives::Rectangle,
+        color: Self::Color,
+    ) -> Result<(), Self::Error> {
+        let (x, y, width, height) = match clip(self, area) {
+            Some(xywh) => xywh,
+            None => return Ok(()),
+        };
+        let length = self.eight_length();
+        let data = self.eight_data_mut().core_mut();
+
+        for row in y..y + height {
+            for cell in (x >> 3)..=((x + width - 1) >> 3) {
+                let start = x.max(cell * 8) - cell * 8;
+                let end = (x + width).min(cell * 8 + 8) - cell * 8;
+                // MSB is the left pixel
+                let mask = span_mask(start, end).reverse_bits();
+                write_mask(&mut data[length * row + cell], mask, color);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<D: EightData> OriginDimensions for VerticalEightPxUintEight<D> {
+    fn size(&self) -> Size {
+        Size::new(self.width() as u32, self.height() as u32)
+    }
+}
+
+impl<D: EightData> DrawTarget for VerticalEightPxUintEight<D> {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (width, height) = (self.width() as i32, self.height() as i32);
+
+        for Pixel(point, color) in pixels {
+            if (0..width).contains(&point.x) && (0..height).contains(&point.y) {
+                self.draw(point.x as usize, point.y as usize, color.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(
+        &mut self,
+        area: &primitives::Rectangle,
+        colors: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let width = self.width();
+        fill_contiguous_by(self, area, colors, |x, y| {
+            (width * (y >> 3) + x, 0b_0000_0001 << (y % 8))
+        });
+
+        Ok(())
+    }
+
+    fn fill_solid(
+        &mut self,
+        area: &primitives::Rectangle,
+        color: Self::Color,
+    ) -> Result<(), Self::Error> {
+        let (x, y, width, height) = match clip(self, area) {
+            Some(xywh) => xywh,
+            None => return Ok(()),
+        };
+        let image_width = self.width();
+        let data = self.eight_data_mut().core_mut();
+
+        for page in (y >> 3)..=((y + height - 1) >> 3) {
+            let start = y.max(page * 8) - page * 8;
+            let end = (y + height).min(page * 8 + 8) - page * 8;
+            // LSB is the top pixel
+            let mask = span_mask(start, end);
+
+            for column in x..x + width {
+                write_mask(&mut data[image_width * page + column], mask, color);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+    use embedded_graphics::mono_font::ascii::FONT_4X6;
+    use embedded_graphics::mono_font::MonoTextStyle;
+    use embedded_graphics::pixelcolor::BinaryColor;
+    use embedded_graphics::prelude::*;
+    use embedded_graphics::primitives::{Line, PrimitiveStyle, Rectangle};
+    use embedded_graphics::text::Text;
+
+    /// Draw the same pixels one by one without the overrides.
+    fn expected<Image: EightPxUintEight>(mut image: Image, pixels: &[Pixel<BinaryColor>]) -> Image {
+        let (width, height) = (image.width() as i32, image.height() as i32);
+        for Pixel(point, color) in pixels {
+            if (0..width).contains(&point.x) && (0..height).contains(&point.y) {
+                image.draw(point.x as usize, point.y as usize, (*color).into());
+            }
+        }
+        image
+    }
+
+    fn area_pixels(area: Rectangle, colors: impl Fn(Point) -> BinaryColor) -> Vec<Pixel<BinaryColor>> {
+        area.points().map(|point| Pixel(point, colors(point))).collect()
+    }
+
+    #[test]
+    fn test_fill_solid() {
+        for area in [
+            Rectangle::new(Point::new(3, 2), Size::new(15, 9)),
+            Rectangle::new(Point::new(-4, -3), Size::new(9, 30)),
+            Rectangle::new(Point::new(8, 8), Size::new(8, 8)),
+            Rectangle::new(Point::new(30, 0), Size::new(4, 4)),
+        ] {
+            let mut h = HorizontalEightPxUintEight::new(20, 13, EightDataClient::new(39)).unwrap();
+            let mut v = VerticalEightPxUintEight::new(20, 13, EightDataClient::new(40)).unwrap();
+            h.fill_solid(&area, BinaryColor::On).unwrap();
+            v.fill_solid(&area, BinaryColor::On).unwrap();
+
+            let pixels = area_pixels(area, |_| BinaryColor::On);
+            assert_eq!(expected(HorizontalEightPxUintEight::new(20, 13, EightDataClient::new(39)).unwrap(), &pixels).as_vec(), h.as_vec());
+            assert_eq!(expected(VerticalEightPxUintEight::new(20, 13, EightDataClient::new(40)).unwrap(), &pixels).as_vec(), v.as_vec());
+        }
+
+        let mut h = HorizontalEightPxUintEight::new(20, 13, EightDataClient::new(39)).unwrap();
+        h.clear(BinaryColor::On).unwrap();
+        h.fill_solid(&Rectangle::new(Point::new(1, 0), Size::new(10, 1)), BinaryColor::Off).unwrap();
+        assert_eq!(&[0b_1000_0000, 0b_0001_1111, 0b_1111_0000], &h.as_vec()[0..3]);
+    }
+
+    #[test]
+    fn test_fill_contiguous() {
+        let checker = |point: Point| match (point.x + point.y) % 3 {
+            0 => BinaryColor::On,
+            _ => BinaryColor::Off,
+        };
+
+        for area in [
+            Rectangle::new(Point::new(3, 2), Size::new(15, 9)),
+            Rectangle::new(Point::new(-4, -3), Size::new(9, 30)),
+        ] {
+            let mut h = HorizontalEightPxUintEight::new(20, 13, EightDataClient::new(39)).unwrap();
+            let mut v = VerticalEightPxUintEight::new(20, 13, EightDataClient::new(40)).unwrap();
+            h.clear(BinaryColor::On).unwrap();
+            v.clear(BinaryColor::On).unwrap();
+
+            let pixels = area_pixels(area, checker);
+            h.fill_contiguous(&area, pixels.iter().map(|p| p.1)).unwrap();
+            v.fill_contiguous(&area, pixels.iter().map(|p| p.1)).unwrap();
+
+            let mut eh = HorizontalEightPxUintEight::new(20, 13, EightDataClient::new(39)).unwrap();
+            let mut ev = VerticalEightPxUintEight::new(20, 13, EightDataClient::new(40)).unwrap();
+            eh.fill((0, 0, 20, 13), Mono::One);
+            ev.fill((0, 0, 20, 13), Mono::One);
+            assert_eq!(expected(eh, &pixels).as_vec(), h.as_vec());
+            assert_eq!(expected(ev, &pixels).as_vec(), v.as_vec());
+        }
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut image = VerticalEightPxUintEight::new(16, 8, EightDataClient::new(16)).unwrap();
+        Line::new(Point::new(0, 0), Point::new(7, 7))
+            .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+            .draw(&mut image)
+            .unwrap();
+        Text::new("A", Point::new(10, 5), MonoTextStyle::new(&FONT_4X6, BinaryColor::On))
+            .draw(&mut image)
+            .unwrap();
+
+        assert_eq!(Size::new(16, 8), image.size());
+        for i in 0..8 {
+            assert_eq!(Mono::One, image.pixel(i, i));
+        }
+        assert!((10..14).any(|x| image.pixel(x, 3) == Mono::One));
+        assert_eq!(&[1, 2, 4, 8, 16, 32, 64, 128], &image.as_vec()[0..8]);
+    }
+}
@@ -0,0 +1,467 @@
+use crate::DisplayInterface;
+use core::convert::Infallible;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, ErrorKind, InputPin, OutputPin, PinState};
+use embedded_hal::i2c::{I2c, Operation};
+use embedded_hal::spi::SpiBus;
+
+/// Milliseconds to hold the reset pin low and to wait after it.
+const RESET_MS: u32 = 10;
+
+/// Microseconds between checks of the busy pin.
+const BUSY_POLL_US: u32 = 100;
+
+/// Milliseconds to wait for the busy pin by default. Full refresh of tri-color e-paper takes about 15 seconds.
+const BUSY_TIMEOUT_MS: u32 = 30_000;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum HalInterfaceError<E> {
+    Bus(E),
+    Pin(ErrorKind),
+    /// The busy pin did not change within the timeout.
+    BusyTimeout,
+}
+
+fn pin<T, E>(result: Result<T, impl digital::Error>) -> Result<T, HalInterfaceError<E>> {
+    result.map_err(|e| HalInterfaceError::Pin(e.kind()))
+}
+
+/// Pin that is not connected. It is never busy.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NoPin;
+
+impl digital::ErrorType for NoPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for NoPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl InputPin for NoPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+/// Delay that returns at once. For interfaces without reset and busy pins.
+///
+/// It is not `DelayNs`, so a busy pin cannot be polled without waiting.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NoDelay;
+
+/// Delay of `SpiInterface`. Any `DelayNs` or `NoDelay`.
+pub trait InterfaceDelay {
+    fn delay_ms(&mut self, ms: u32);
+    fn delay_us(&mut self, us: u32);
+}
+
+impl<D: DelayNs> InterfaceDelay for D {
+    fn delay_ms(&mut self, ms: u32) {
+        DelayNs::delay_ms(self, ms)
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        DelayNs::delay_us(self, us)
+    }
+}
+
+impl InterfaceDelay for NoDelay {
+    fn delay_ms(&mut self, _ms: u32) {}
+
+    fn delay_us(&mut self, _us: u32) {}
+}
+
+/// 4-wire SPI. DC is low for commands and high for data.
+pub struct SpiInterface<SPI, DC, CS, RST = NoPin, BUSY = NoPin, DELAY = NoDelay> {
+    spi: SPI,
+    dc: DC,
+    cs: CS,
+    rst: RST,
+    busy: BUSY,
+    busy_level: PinState,
+    busy_timeout_ms: u32,
+    delay: DELAY,
+}
+
+impl<SPI, DC, CS> SpiInterface<SPI, DC, CS> {
+    pub fn new(spi: SPI, dc: DC, cs: CS) -> Self {
+        Self {
+            spi,
+            dc,
+            cs,
+            rst: NoPin,
+            busy: NoPin,
+            busy_level: PinState::High,
+            busy_timeout_ms: BUSY_TIMEOUT_MS,
+            delay: NoDelay,
+        }
+    }
+}
+
+impl<SPI, DC, CS, RST, BUSY, DELAY> SpiInterface<SPI, DC, CS, RST, BUSY, DELAY> {
+    /// Use the reset pin. The delay is also used to poll the busy pin.
+    pub fn with_reset<R, D: DelayNs>(
+        self,
+        rst: R,
+        delay: D,
+    ) -> SpiInterface<SPI, DC, CS, R, BUSY, D> {
+        SpiInterface {
+            spi: self.spi,
+            dc: self.dc,
+            cs: self.cs,
+            rst,
+            busy: self.busy,
+            busy_level: self.busy_level,
+            busy_timeout_ms: self.busy_timeout_ms,
+            delay,
+        }
+    }
+
+    /// Use the delay to poll the busy pin without a reset pin.
+    pub fn with_delay<D: DelayNs>(self, delay: D) -> SpiInterface<SPI, DC, CS, RST, BUSY, D> {
+        SpiInterface {
+            spi: self.spi,
+            dc: self.dc,
+            cs: self.cs,
+            rst: self.rst,
+            busy: self.busy,
+            busy_level: self.busy_level,
+            busy_timeout_ms: self.busy_timeout_ms,
+            delay,
+        }
+    }
+
+    /// Give up waiting for the busy pin after the time. It is measured by the delay.
+    pub fn with_busy_timeout(self, busy_timeout_ms: u32) -> Self {
+        Self {
+            busy_timeout_ms,
+            ..self
+        }
+    }
+
+    pub fn release(self) -> (SPI, DC, CS, RST, BUSY, DELAY) {
+        (self.spi, self.dc, self.cs, self.rst, self.busy, self.delay)
+    }
+}
+
+impl<SPI, DC, CS, RST, BUSY, DELAY: DelayNs> SpiInterface<SPI, DC, CS, RST, BUSY, DELAY> {
+    /// Use the busy pin. The controller is busy while the pin is at `busy_level`.
+    ///
+    /// The busy pin is polled with the delay, so `with_reset` or `with_delay` comes first.
+    pub fn with_busy<B>(
+        self,
+        busy: B,
+        busy_level: PinState,
+    ) -> SpiInterface<SPI, DC, CS, RST, B, DELAY> {
+        SpiInterface {
+            spi: self.spi,
+            dc: self.dc,
+            cs: self.cs,
+            rst: self.rst,
+            busy,
+            busy_level,
+            busy_timeout_ms: self.busy_timeout_ms,
+            delay: self.delay,
+        }
+    }
+}
+
+impl<SPI, DC, CS, RST, BUSY, DELAY> SpiInterface<SPI, DC, CS, RST, BUSY, DELAY>
+where
+    SPI: SpiBus,
+    DC: OutputPin,
+    CS: OutputPin,
+{
+    fn write(&mut self, dc: PinState, bytes: &[u8]) -> Result<(), HalInterfaceError<SPI::Error>> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+
+        pin(self.dc.set_state(dc))?;
+        pin(self.cs.set_low())?;
+        let written = self
+            .spi
+            .write(bytes)
+            .and_then(|_| self.spi.flush())
+            .map_err(HalInterfaceError::Bus);
+        pin(self.cs.set_high())?;
+
+        written
+    }
+}
+
+impl<SPI, DC, CS, RST, BUSY, DELAY> DisplayInterface for SpiInterface<SPI, DC, CS, RST, BUSY, DELAY>
+where
+    SPI: SpiBus,
+    DC: OutputPin,
+    CS: OutputPin,
+    RST: OutputPin,
+    BUSY: InputPin,
+    DELAY: InterfaceDelay,
+{
+    type Error = HalInterfaceError<SPI::Error>;
+
+    fn send_command(&mut self, command: u8) -> Result<(), Self::Error> {
+        self.write(PinState::Low, &[command])
+    }
+
+    fn send_commands(&mut self, commands: &[u8]) -> Result<(), Self::Error> {
+        self.write(PinState::Low, commands)
+    }
+
+    fn send_data(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.write(PinState::High, data)
+    }
+
+    fn wait_busy(&mut self) -> Result<(), Self::Error> {
+        let polls = self.busy_timeout_ms as u64 * 1000 / BUSY_POLL_US as u64;
+        let mut polled = 0;
+
+        loop {
+            let busy = match self.busy_level {
+                PinState::High => pin(self.busy.is_high())?,
+                PinState::Low => pin(self.busy.is_low())?,
+            };
+
+            if !busy {
+                return Ok(());
+            }
+            if polled >= polls {
+                return Err(HalInterfaceError::BusyTimeout);
+            }
+
+            self.delay.delay_us(BUSY_POLL_US);
+            polled += 1;
+        }
+    }
+
+    fn reset(&mut self) -> Result<(), Self::Error> {
+        pin(self.rst.set_low())?;
+        self.delay.delay_ms(RESET_MS);
+        pin(self.rst.set_high())?;
+        self.delay.delay_ms(RESET_MS);
+
+        Ok(())
+    }
+}
+
+/// I2C of SSD1306-class controllers. A control byte leads every transfer.
+pub struct I2cInterface<I2C> {
+    i2c: I2C,
+    address: u8,
+}
+
+impl<I2C> I2cInterface<I2C> {
+    /// Control byte of commands.
+    pub const COMMAND: u8 = 0x00;
+    /// Control byte of data.
+    pub const DATA: u8 = 0x40;
+
+    pub fn new(i2c: I2C, address: u8) -> Self {
+        Self { i2c, address }
+    }
+
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C: I2c> I2cInterface<I2C> {
+    fn write(&mut self, control: u8, bytes: &[u8]) -> Result<(), HalInterfaceError<I2C::Error>> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+
+        // adjacent writes are sent without a repeated start
+        self.i2c
+            .transaction(
+                self.address,
+                &mut [Operation::Write(&[control]), Operation::Write(bytes)],
+            )
+            .map_err(HalInterfaceError::Bus)
+    }
+}
+
+impl<I2C: I2c> DisplayInterface for I2cInterface<I2C> {
+    type Error = HalInterfaceError<I2C::Error>;
+
+    fn send_command(&mut self, command: u8) -> Result<(), Self::Error> {
+        self.write(Self::COMMAND, &[command])
+    }
+
+    fn send_commands(&mut self, commands: &[u8]) -> Result<(), Self::Error> {
+        self.write(Self::COMMAND, commands)
+    }
+
+    fn send_data(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.write(Self::DATA, data)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use embedded_hal::digital::PinState;
+    use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+    use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+    use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+
+    #[test]
+    fn test_spi() {
+        let mut spi = SpiMock::new(&[
+            SpiTransaction::write_vec(vec![0xae]),
+            SpiTransaction::flush(),
+            SpiTransaction::write_vec(vec![0x21, 0, 127]),
+            SpiTransaction::flush(),
+            SpiTransaction::write_vec(vec![1, 2, 3]),
+            SpiTransaction::flush(),
+        ]);
+        let mut dc = PinMock::new(&[
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::High),
+        ]);
+        let cs_frame = [PinTransaction::set(State::Low), PinTransaction::set(State::High)];
+        let mut cs = PinMock::new(&[cs_frame.clone(), cs_frame.clone(), cs_frame].concat());
+
+        let mut interface = SpiInterface::new(spi.clone(), dc.clone(), cs.clone());
+        interface.send_command(0xae).unwrap();
+        interface.send_commands(&[0x21, 0, 127]).unwrap();
+        interface.send_data(&[1, 2, 3]).unwrap();
+        interface.send_data(&[]).unwrap();
+        interface.reset().unwrap();
+        interface.wait_busy().unwrap();
+
+        spi.done();
+        dc.done();
+        cs.done();
+    }
+
+    #[test]
+    fn test_spi_pins() {
+        let mut spi = SpiMock::new(&[]);
+        let mut dc = PinMock::new(&[]);
+        let mut cs = PinMock::new(&[]);
+        let mut rst = PinMock::new(&[PinTransaction::set(State::Low), PinTransaction::set(State::High)]);
+        let mut busy = PinMock::new(&[
+            PinTransaction::get(State::Low),
+            PinTransaction::get(State::Low),
+            PinTransaction::get(State::High),
+        ]);
+        let mut delay = CheckedDelay::new(&[
+            DelayTransaction::delay_ms(10),
+            DelayTransaction::delay_ms(10),
+            DelayTransaction::delay_us(100),
+            DelayTransaction::delay_us(100),
+        ]);
+
+        let mut interface = SpiInterface::new(spi.clone(), dc.clone(), cs.clone())
+            .with_reset(rst.clone(), delay.clone())
+            .with_busy(busy.clone(), PinState::Low);
+        interface.reset().unwrap();
+        interface.wait_busy().unwrap();
+
+        spi.done();
+        dc.done();
+        cs.done();
+        rst.done();
+        busy.done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_busy_timeout() {
+        let mut spi = SpiMock::new(&[]);
+        let mut dc = PinMock::new(&[]);
+        let mut cs = PinMock::new(&[]);
+        // busy for 1ms: 11 checks and 10 polls
+        let mut busy = PinMock::new(&vec![PinTransaction::get(State::High); 11]);
+        let mut delay = CheckedDelay::new(&vec![DelayTransaction::delay_us(100); 10]);
+
+        let mut interface = SpiInterface::new(spi.clone(), dc.clone(), cs.clone())
+            .with_delay(delay.clone())
+            .with_busy(busy.clone(), PinState::High)
+            .with_busy_timeout(1);
+        assert_eq!(Err(HalInterfaceError::BusyTimeout), interface.wait_busy());
+
+        spi.done();
+        dc.done();
+        cs.done();
+        busy.done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_i2c() {
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::transaction_start(0x3c),
+            I2cTransaction::write(0x3c, vec![0x00]),
+            I2cTransaction::write(0x3c, vec![0xaf]),
+            I2cTransaction::transaction_end(0x3c),
+            I2cTransaction::transaction_start(0x3c),
+            I2cTransaction::write(0x3c, vec![0x00]),
+            I2cTransaction::write(0x3c, vec![0x22, 0, 7]),
+            I2cTransaction::transaction_end(0x3c),
+            I2cTransaction::transaction_start(0x3c),
+            I2cTransaction::write(0x3c, vec![0x40]),
+            I2cTransaction::write(0x3c, vec![0xff, 0x00]),
+            I2cTransaction::transaction_end(0x3c),
+        ]);
+
+        let mut interface = I2cInterface::new(i2c.clone(), 0x3c);
+        interface.send_command(0xaf).unwrap();
+        interface.send_commands(&[0x22, 0, 7]).unwrap();
+        interface.send_data(&[0xff, 0x00]).unwrap();
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_flush() {
+        let mut data = [0; 4];
+        let mut image = HorizontalEightPxUintEight::new(16, 2, &mut data[..]).unwrap();
+        image.fill((0, 1, 8, 1), Mono::One);
+
+        struct Controller;
+
+        impl RamWindow for Controller {
+            fn set_window<I: DisplayInterface>(&mut self, interface: &mut I, window: Rectangle) -> Result<(), I::Error> {
+                interface.send_command_with_data(0x44, &[window.x as u8, (window.x + window.width - 1) as u8])
+            }
+        }
+
+        let mut i2c = I2cMock::new(&[
+            I2cTransaction::transaction_start(0x3c),
+            I2cTransaction::write(0x3c, vec![0x00]),
+            I2cTransaction::write(0x3c, vec![0x44]),
+            I2cTransaction::transaction_end(0x3c),
+            I2cTransaction::transaction_start(0x3c),
+            I2cTransaction::write(0x3c, vec![0x40]),
+            I2cTransaction::write(0x3c, vec![0, 1]),
+            I2cTransaction::transaction_end(0x3c),
+            I2cTransaction::transaction_start(0x3c),
+            I2cTransaction::write(0x3c, vec![0x40]),
+            I2cTransaction::write(0x3c, vec![0xff, 0x00]),
+            I2cTransaction::transaction_end(0x3c),
+        ]);
+
+        let mut interface = I2cInterface::new(i2c.clone(), 0x3c);
+        flush(&image, (0, 1, 16, 1), &mut Controller, &mut interface).unwrap();
+
+        i2c.done();
+    }
+}
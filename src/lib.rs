@@ -1,20 +1,54 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod bmp;
+mod brother_ql;
+mod canvas;
 mod common;
+mod container;
+mod dither;
+mod display_interface;
+mod drivers;
 mod eight_data;
 mod eight_px_uint_eight;
+mod escpos;
 mod error;
+#[cfg(feature = "embedded-graphics")]
+mod graphics;
+#[cfg(feature = "embedded-hal")]
+mod hal_interface;
 mod horizontal_eight_px_uint_eight;
+mod packbits;
+mod pbm;
+mod preview;
+#[cfg(feature = "serde")]
+mod serialize;
+mod threshold;
 mod vertical_eight_px_uint_eight;
+mod xbm;
 
 #[cfg(feature = "std")]
 pub mod unix;
 
 pub use crate::eight_px_uint_eight::*;
+pub use bmp::*;
+pub use brother_ql::*;
+pub use canvas::*;
 pub use common::*;
+pub use container::*;
+pub use dither::*;
+pub use display_interface::*;
+pub use drivers::*;
 pub use eight_data::*;
 pub use error::*;
+pub use escpos::*;
+#[cfg(feature = "embedded-hal")]
+pub use hal_interface::*;
 pub use horizontal_eight_px_uint_eight::*;
+pub use packbits::*;
+pub use pbm::*;
+pub use preview::*;
+pub use threshold::*;
 pub use vertical_eight_px_uint_eight::*;
+pub use xbm::*;
 
 pub type EightPxUintEightResult<T> = Result<T, EightPxUintEightError>;
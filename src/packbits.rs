@@ -0,0 +1,162 @@
+use crate::*;
+
+/// The longest length of `src.len()` bytes compressed by PackBits.
+pub const fn pack_bits_max_length(length: usize) -> usize {
+    length + length.div_ceil(128)
+}
+
+fn put(dst: &mut [u8], i: usize, byte: u8) -> EightPxUintEightResult<()> {
+    match dst.get_mut(i) {
+        Some(d) => {
+            *d = byte;
+            Ok(())
+        }
+        None => Err(EightPxUintEightError::Overflow((dst.len(), i))),
+    }
+}
+
+/// Same as `pack_bits` but every byte is mapped before compression.
+pub(crate) fn pack_bits_by(
+    src: &[u8],
+    dst: &mut [u8],
+    map: impl Fn(u8) -> u8,
+) -> EightPxUintEightResult<usize> {
+    let at = |i: usize| map(src[i]);
+    let (mut i, mut written) = (0, 0);
+
+    while i < src.len() {
+        let run = (i..src.len().min(i + 128))
+            .take_while(|j| at(*j) == at(i))
+            .count();
+
+        if run >= 2 {
+            put(dst, written, (1 - run as i16) as u8)?;
+            put(dst, written + 1, at(i))?;
+            written += 2;
+            i += run;
+            continue;
+        }
+
+        // literal until the next run of 3 or more. breaking for 2 would cost a header and save nothing
+        let mut end = i + 1;
+        while end < src.len()
+            && end - i < 128
+            && !(end + 2 < src.len() && at(end) == at(end + 1) && at(end) == at(end + 2))
+        {
+            end += 1;
+        }
+
+        put(dst, written, (end - i - 1) as u8)?;
+        for j in i..end {
+            put(dst, written + 1 + j - i, at(j))?;
+        }
+        written += 1 + end - i;
+        i = end;
+    }
+
+    Ok(written)
+}
+
+/// Compress by PackBits (TIFF, Brother QL). Return the length written into `dst`.
+pub fn pack_bits(src: &[u8], dst: &mut [u8]) -> EightPxUintEightResult<usize> {
+    pack_bits_by(src, dst, |byte| byte)
+}
+
+/// Decompress PackBits. Return the length written into `dst`.
+pub fn unpack_bits(src: &[u8], dst: &mut [u8]) -> EightPxUintEightResult<usize> {
+    let (mut i, mut written) = (0, 0);
+
+    while i < src.len() {
+        let header = src[i] as i8;
+
+        match header {
+            0..=127 => {
+                let length = header as usize + 1;
+                let literal = src
+                    .get(i + 1..i + 1 + length)
+                    .ok_or(EightPxUintEightError::InvalidFormat)?;
+                for byte in literal {
+                    put(dst, written, *byte)?;
+                    written += 1;
+                }
+                i += 1 + length;
+            }
+            -127..=-1 => {
+                let byte = *src.get(i + 1).ok_or(EightPxUintEightError::InvalidFormat)?;
+                for _ in 0..(1 - header as isize) {
+                    put(dst, written, byte)?;
+                    written += 1;
+                }
+                i += 2;
+            }
+            // no operation
+            -128 => i += 1,
+        }
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test_pack_bits() {
+        // the example of Apple's technical note TN1023
+        let src = [
+            0xaa, 0xaa, 0xaa, 0x80, 0x00, 0x2a, 0xaa, 0xaa, 0xaa, 0xaa, 0x80, 0x00,
+            0x2a, 0x22, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa,
+        ];
+        let packed = [
+            0xfe, 0xaa, 0x02, 0x80, 0x00, 0x2a, 0xfd, 0xaa, 0x03, 0x80, 0x00, 0x2a,
+            0x22, 0xf7, 0xaa,
+        ];
+
+        let mut dst = [0; 32];
+        let length = pack_bits(&src, &mut dst).unwrap();
+        assert_eq!(&packed, &dst[..length]);
+
+        let mut unpacked = [0; 24];
+        assert_eq!(Ok(24), unpack_bits(&packed, &mut unpacked));
+        assert_eq!(src, unpacked);
+    }
+
+    #[test]
+    fn test_long() {
+        let mut src = [0; 300];
+        for (i, byte) in src.iter_mut().enumerate().skip(200) {
+            *byte = i as u8;
+        }
+
+        let mut dst = [0; pack_bits_max_length(300)];
+        let length = pack_bits(&src, &mut dst).unwrap();
+        // 128 + 72 zeros and 100 literal bytes
+        assert_eq!(2 + 2 + 1 + 100, length);
+
+        let mut unpacked = [0xff; 300];
+        assert_eq!(Ok(300), unpack_bits(&dst[..length], &mut unpacked));
+        assert_eq!(src, unpacked);
+    }
+
+    #[test]
+    fn test_short_runs() {
+        // runs of 2 stay in the literal, so the longest length holds
+        let src = [[0xaa, 0xbb, 0xbb]; 10].concat();
+
+        let mut dst = [0; pack_bits_max_length(30)];
+        assert_eq!(Ok(31), pack_bits(&src, &mut dst));
+
+        let mut unpacked = [0; 30];
+        assert_eq!(Ok(30), unpack_bits(&dst, &mut unpacked));
+        assert_eq!(src, unpacked);
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(Err(EightPxUintEightError::Overflow((1, 1))), pack_bits(&[1, 2], &mut [0; 1]));
+        assert_eq!(Err(EightPxUintEightError::Overflow((2, 2))), unpack_bits(&[0xfd, 0], &mut [0; 2]));
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), unpack_bits(&[0x02, 0], &mut [0; 8]));
+    }
+}
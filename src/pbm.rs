@@ -0,0 +1,317 @@
+use crate::*;
+use core::cmp::min;
+
+/// Netpbm bitmap. P1 is ASCII, P4 is binary.
+///
+/// A set bit of PBM is black, and it is treated as `Mono::One`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum PbmKind {
+    P1,
+    P4,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Pbm<'a> {
+    kind: PbmKind,
+    width: usize,
+    height: usize,
+    body: &'a [u8],
+}
+
+impl<'a> Pbm<'a> {
+    pub fn parse(src: &'a [u8]) -> EightPxUintEightResult<Self> {
+        let kind = match src.get(0..2) {
+            Some(b"P1") => PbmKind::P1,
+            Some(b"P4") => PbmKind::P4,
+            _ => return Err(EightPxUintEightError::InvalidFormat),
+        };
+
+        let mut cursor = Cursor::new(src, 2);
+        let width = cursor.number()?;
+        let height = cursor.number()?;
+
+        let body = match kind {
+            PbmKind::P1 => {
+                let body = &src[cursor.i..];
+                // every pixel is a digit at least, so a short body fails before any allocation
+                let pixels = width
+                    .checked_mul(height)
+                    .ok_or(EightPxUintEightError::InvalidFormat)?;
+                if body.len() < pixels {
+                    return Err(EightPxUintEightError::InvalidLengthData);
+                }
+                body
+            }
+            PbmKind::P4 => {
+                // exactly one whitespace separates the header from the raster
+                let start = cursor.i + 1;
+                let end = HorizontalEightPxUintEight::<&mut [u8]>::data_length(width, height)
+                    .ok()
+                    .and_then(|length| length.checked_add(start))
+                    .ok_or(EightPxUintEightError::InvalidFormat)?;
+                match src.get(start..end) {
+                    Some(body) => body,
+                    None => return Err(EightPxUintEightError::InvalidLengthData),
+                }
+            }
+        };
+
+        Ok(Self {
+            kind,
+            width,
+            height,
+            body,
+        })
+    }
+
+    pub fn kind(&self) -> PbmKind {
+        self.kind
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Return the raster of P4. It is the same layout as `HorizontalEightPxUintEight`.
+    pub fn raster(&self) -> Option<&'a [u8]> {
+        match self.kind {
+            PbmKind::P1 => None,
+            PbmKind::P4 => Some(self.body),
+        }
+    }
+
+    /// Draw pixels from (0, 0). Pixels that overflow are discarded.
+    pub fn write_into(&self, image: &mut impl EightPxUintEight) -> EightPxUintEightResult<()> {
+        let width = min(self.width, image.width());
+        let height = min(self.height, image.height());
+
+        match self.kind {
+            PbmKind::P1 => {
+                let mut cursor = Cursor::new(self.body, 0);
+
+                for y in 0..self.height {
+                    for x in 0..self.width {
+                        let color = match cursor.digit()? {
+                            b'0' => Mono::Zero,
+                            _ => Mono::One,
+                        };
+
+                        if x < width && y < height {
+                            image.draw(x, y, color);
+                        }
+                    }
+                }
+            }
+            PbmKind::P4 => {
+                let eight_length = compute_eight_length(self.width);
+
+                for y in 0..height {
+                    for x in 0..width {
+                        let byte = self.body[eight_length * y + (x >> 3)];
+                        let color = match byte & (0b_1000_0000 >> (x % 8)) {
+                            0 => Mono::Zero,
+                            _ => Mono::One,
+                        };
+
+                        image.draw(x, y, color);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Cursor<'a> {
+    src: &'a [u8],
+    i: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(src: &'a [u8], i: usize) -> Self {
+        Self { src, i }
+    }
+
+    fn skip(&mut self) {
+        while let Some(c) = self.src.get(self.i) {
+            match c {
+                b'#' => {
+                    while !matches!(self.src.get(self.i), None | Some(b'\n') | Some(b'\r')) {
+                        self.i += 1;
+                    }
+                }
+                c if c.is_ascii_whitespace() => self.i += 1,
+                _ => return,
+            }
+        }
+    }
+
+    fn number(&mut self) -> EightPxUintEightResult<usize> {
+        self.skip();
+
+        let start = self.i;
+        let mut n: usize = 0;
+
+        while let Some(c) = self.src.get(self.i).filter(|c| c.is_ascii_digit()) {
+            n = n
+                .checked_mul(10)
+                .and_then(|n| n.checked_add((c - b'0') as usize))
+                .ok_or(EightPxUintEightError::InvalidFormat)?;
+            self.i += 1;
+        }
+
+        if start == self.i {
+            return Err(EightPxUintEightError::InvalidFormat);
+        }
+
+        Ok(n)
+    }
+
+    fn digit(&mut self) -> EightPxUintEightResult<u8> {
+        self.skip();
+
+        match self.src.get(self.i) {
+            Some(c @ b'0') | Some(c @ b'1') => {
+                self.i += 1;
+                Ok(*c)
+            }
+            _ => Err(EightPxUintEightError::InvalidFormat),
+        }
+    }
+}
+
+/// Encode the image into PBM.
+#[cfg(feature = "std")]
+pub fn to_pbm(image: &impl EightPxUintEight, kind: PbmKind) -> Vec<u8> {
+    let (width, height) = (image.width(), image.height());
+
+    match kind {
+        PbmKind::P1 => {
+            let mut pbm = format!("P1\n{} {}\n", width, height).into_bytes();
+
+            for y in 0..height {
+                for x in 0..width {
+                    pbm.push(match image.pixel(x, y) {
+                        Mono::Zero => b'0',
+                        Mono::One => b'1',
+                    });
+
+                    // lines should not be longer than 70 characters
+                    pbm.push(match x + 1 {
+                        n if n == width || n % 35 == 0 => b'\n',
+                        _ => b' ',
+                    });
+                }
+            }
+
+            pbm
+        }
+        PbmKind::P4 => {
+            let mut pbm = format!("P4\n{} {}\n", width, height).into_bytes();
+
+            for y in 0..height {
+                for eight_x in 0..compute_eight_length(width) {
+                    let mut byte = 0;
+
+                    for step_x in 0..min(8, width - eight_x * 8) {
+                        if image.pixel(eight_x * 8 + step_x, y) == Mono::One {
+                            byte |= 0b_1000_0000 >> step_x;
+                        }
+                    }
+
+                    pbm.push(byte);
+                }
+            }
+
+            pbm
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::EightDataClient;
+
+    #[test]
+    fn test_parse_p1() {
+        let src = b"P1\n# comment\n5 3\n1 1 0 0 0\n1 0 0 0 1\n0 0 0 0 0\n";
+        let pbm = Pbm::parse(src).unwrap();
+
+        assert_eq!((PbmKind::P1, 5, 3), (pbm.kind(), pbm.width(), pbm.height()));
+        assert_eq!(None, pbm.raster());
+
+        let mut image = VerticalEightPxUintEight::new(5, 3, EightDataClient::new(5)).unwrap();
+        pbm.write_into(&mut image).unwrap();
+
+        assert_eq!(
+            [
+                0b_0000_0011,
+                0b_0000_0001,
+                0b_0000_0000,
+                0b_0000_0000,
+                0b_0000_0010,
+            ],
+            image.as_vec()
+        );
+    }
+
+    #[test]
+    fn test_parse_p1_without_space() {
+        let pbm = Pbm::parse(b"P1 3 2 101 010").unwrap();
+        let mut image = HorizontalEightPxUintEight::new(3, 2, EightDataClient::new(2)).unwrap();
+        pbm.write_into(&mut image).unwrap();
+
+        assert_eq!([0b_1010_0000, 0b_0100_0000], image.as_vec());
+    }
+
+    #[test]
+    fn test_parse_p4() {
+        let src = [b"P4\n11 2\n" as &[u8], &[0b_1100_0010, 0b_0100_0000, 0b_1000_1001, 0b_1010_0000]].concat();
+        let pbm = Pbm::parse(&src).unwrap();
+
+        assert_eq!(Some(&src[8..]), pbm.raster());
+
+        let image = HorizontalEightPxUintEight::new(
+            pbm.width(),
+            pbm.height(),
+            EightDataClient::with_data(pbm.raster().unwrap().to_vec()),
+        ).unwrap();
+
+        assert_eq!(Mono::One, image.pixel(8, 1));
+        assert_eq!(src, to_pbm(&image, PbmKind::P4));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), Pbm::parse(b"P2\n1 1\n0"));
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), Pbm::parse(b"P1\n1"));
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), Pbm::parse(b"P4\n1 18446744073709551615\n"));
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), Pbm::parse(b"P4\n18446744073709551615 9\n"));
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), Pbm::parse(b"P4\n9 2\n\x00\x00\x00"));
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), Pbm::parse(b"P1 18446744073709551615 18446744073709551615 "));
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), Pbm::parse(b"P1 100000 100000 1"));
+
+        let pbm = Pbm::parse(b"P1\n2 2\n1 0 1").unwrap();
+        let mut image = HorizontalEightPxUintEight::new(2, 2, EightDataClient::new(2)).unwrap();
+        assert_eq!(Err(EightPxUintEightError::InvalidFormat), pbm.write_into(&mut image));
+    }
+
+    #[test]
+    fn test_to_pbm_p1() {
+        let mut image = HorizontalEightPxUintEight::new(3, 2, EightDataClient::new(2)).unwrap();
+        image.update((0, 0, 3, 2), &[
+            1, 0, 1,
+            0, 1, 0,
+        ]).unwrap();
+
+        assert_eq!(b"P1\n3 2\n1 0 1\n0 1 0\n".to_vec(), to_pbm(&image, PbmKind::P1));
+    }
+}
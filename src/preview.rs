@@ -0,0 +1,166 @@
+use crate::*;
+use core::fmt;
+
+/// Characters to render images on terminals.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum PreviewStyle {
+    /// `▀▄█`, 1 * 2 pixels per character.
+    HalfBlock,
+    /// Braille dots, 2 * 4 pixels per character.
+    Braille,
+    /// `#` and `.`, 1 pixel per character.
+    Ascii,
+}
+
+fn is_one(image: &impl EightPxUintEight, x: usize, y: usize) -> bool {
+    x < image.width() && y < image.height() && image.pixel(x, y) == Mono::One
+}
+
+/// Write the image as lines of characters. `Mono::One` is a filled character.
+pub fn render(
+    image: &impl EightPxUintEight,
+    style: PreviewStyle,
+    f: &mut impl fmt::Write,
+) -> fmt::Result {
+    let (width, height) = (image.width(), image.height());
+
+    match style {
+        PreviewStyle::HalfBlock => {
+            for y in (0..height).step_by(2) {
+                for x in 0..width {
+                    f.write_char(match (is_one(image, x, y), is_one(image, x, y + 1)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    })?;
+                }
+                f.write_char('\n')?;
+            }
+        }
+        PreviewStyle::Braille => {
+            // bits of dots in the order of (dx, dy)
+            const DOTS: [(usize, usize, u32); 8] = [
+                (0, 0, 0x01),
+                (0, 1, 0x02),
+                (0, 2, 0x04),
+                (1, 0, 0x08),
+                (1, 1, 0x10),
+                (1, 2, 0x20),
+                (0, 3, 0x40),
+                (1, 3, 0x80),
+            ];
+
+            for y in (0..height).step_by(4) {
+                for x in (0..width).step_by(2) {
+                    let dots = DOTS
+                        .iter()
+                        .filter(|(dx, dy, _)| is_one(image, x + dx, y + dy))
+                        .fold(0, |dots, (_, _, bit)| dots | bit);
+
+                    f.write_char(char::from_u32(0x2800 + dots).unwrap_or(' '))?;
+                }
+                f.write_char('\n')?;
+            }
+        }
+        PreviewStyle::Ascii => {
+            for y in 0..height {
+                for x in 0..width {
+                    f.write_char(match is_one(image, x, y) {
+                        true => '#',
+                        false => '.',
+                    })?;
+                }
+                f.write_char('\n')?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Display and Debug view of an image.
+pub struct Preview<'a, T: EightPxUintEight> {
+    image: &'a T,
+    style: PreviewStyle,
+}
+
+impl<'a, T: EightPxUintEight> Preview<'a, T> {
+    pub fn new(image: &'a T, style: PreviewStyle) -> Self {
+        Self { image, style }
+    }
+}
+
+impl<'a, T: EightPxUintEight> fmt::Display for Preview<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render(self.image, self.style, f)
+    }
+}
+
+impl<'a, T: EightPxUintEight> fmt::Debug for Preview<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} x {}", self.image.width(), self.image.height())?;
+        render(self.image, self.style, f)
+    }
+}
+
+impl<D: EightData> fmt::Display for HorizontalEightPxUintEight<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render(self, PreviewStyle::HalfBlock, f)
+    }
+}
+
+impl<D: EightData> fmt::Display for VerticalEightPxUintEight<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render(self, PreviewStyle::HalfBlock, f)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::{horizontal, EightDataClient};
+
+    const PIXELS: [u8; 9] = [
+        1, 0, 1,
+        1, 1, 0,
+        0, 1, 1,
+    ];
+
+    #[test]
+    fn test_ascii() {
+        let image = horizontal(3, 3, &PIXELS);
+        assert_eq!(
+            "#.#\n##.\n.##\n",
+            image.preview(PreviewStyle::Ascii).to_string()
+        );
+    }
+
+    #[test]
+    fn test_half_block() {
+        let image = horizontal(3, 3, &PIXELS);
+        assert_eq!("█▄▀\n ▀▀\n", image.to_string());
+
+        let mut vertical = VerticalEightPxUintEight::new(3, 3, EightDataClient::new(3)).unwrap();
+        vertical.blit(0, 0, &image, (0, 0, 3, 3));
+        assert_eq!("█▄▀\n ▀▀\n", vertical.to_string());
+    }
+
+    #[test]
+    fn test_braille() {
+        let image = horizontal(3, 3, &PIXELS);
+        // ⠳ has dots 1, 2, 5, 6 and ⠅ has dots 1, 3
+        assert_eq!("⠳⠅\n", image.preview(PreviewStyle::Braille).to_string());
+    }
+
+    #[test]
+    fn test_debug() {
+        let image = horizontal(3, 3, &PIXELS);
+        assert_eq!(
+            "3 x 3\n#.#\n##.\n.##\n",
+            format!("{:?}", image.preview(PreviewStyle::Ascii))
+        );
+    }
+}
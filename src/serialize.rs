@@ -0,0 +1,271 @@
+use crate::*;
+use core::fmt;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Packed bytes. JSON has them as an array of numbers and CBOR as a byte string.
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename = "EightPxUintEight")]
+struct RawRef<'a> {
+    width: usize,
+    height: usize,
+    layout: Layout,
+    data: Bytes<'a>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename = "EightPxUintEight")]
+struct Raw<D> {
+    width: usize,
+    height: usize,
+    layout: Layout,
+    data: D,
+}
+
+fn serialize_image<S: Serializer>(
+    image: &impl EightPxUintEight,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    RawRef {
+        width: image.width(),
+        height: image.height(),
+        layout: image.layout(),
+        data: Bytes(image.as_vec()),
+    }
+    .serialize(serializer)
+}
+
+/// Check the layout tag and the length of data by `new`.
+fn deserialize_image<'de, De, D, T>(
+    deserializer: De,
+    layout: Layout,
+    new: impl FnOnce(usize, usize, D) -> EightPxUintEightResult<T>,
+) -> Result<T, De::Error>
+where
+    De: Deserializer<'de>,
+    D: Deserialize<'de>,
+{
+    let raw = Raw::<D>::deserialize(deserializer)?;
+
+    if raw.layout != layout {
+        return Err(de::Error::custom(format_args!(
+            "expected {:?} layout but {:?}",
+            layout, raw.layout
+        )));
+    }
+
+    // tell a size too large for memory from a wrong length of data
+    let length = match layout {
+        Layout::Horizontal => {
+            HorizontalEightPxUintEight::<&mut [u8]>::data_length(raw.width, raw.height)
+        }
+        Layout::Vertical => {
+            VerticalEightPxUintEight::<&mut [u8]>::data_length(raw.width, raw.height)
+        }
+    };
+    if length.is_err() {
+        return Err(de::Error::custom(format_args!(
+            "too large image: {} x {}",
+            raw.width, raw.height
+        )));
+    }
+
+    new(raw.width, raw.height, raw.data)
+        .map_err(|e| de::Error::custom(format_args!("invalid image: {:?}", e)))
+}
+
+impl<D: EightData> Serialize for HorizontalEightPxUintEight<D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_image(self, serializer)
+    }
+}
+
+impl<'de, D: EightData + Deserialize<'de>> Deserialize<'de> for HorizontalEightPxUintEight<D> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        deserialize_image(deserializer, Layout::Horizontal, Self::new)
+    }
+}
+
+impl<D: EightData> Serialize for VerticalEightPxUintEight<D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_image(self, serializer)
+    }
+}
+
+impl<'de, D: EightData + Deserialize<'de>> Deserialize<'de> for VerticalEightPxUintEight<D> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        deserialize_image(deserializer, Layout::Vertical, Self::new)
+    }
+}
+
+impl<const N: usize> Serialize for ArrayEightData<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.core())
+    }
+}
+
+struct ArrayVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for ArrayVisitor<N> {
+    type Value = ArrayEightData<N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bytes", N)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        if v.len() != N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+
+        let mut data = ArrayEightData::default();
+        data.core_mut().copy_from_slice(v);
+
+        Ok(data)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut data = ArrayEightData::default();
+
+        for (i, byte) in data.core_mut().iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(N + 1, &self));
+        }
+
+        Ok(data)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for ArrayEightData<N> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        deserializer.deserialize_bytes(ArrayVisitor::<N>)
+    }
+}
+
+#[cfg(feature = "std")]
+mod client {
+    use super::*;
+    use crate::unix::EightDataClient;
+
+    impl Serialize for EightDataClient {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(self.core())
+        }
+    }
+
+    struct ClientVisitor;
+
+    impl<'de> Visitor<'de> for ClientVisitor {
+        type Value = EightDataClient;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("bytes")
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            Ok(EightDataClient::with_data(v.to_vec()))
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+            Ok(EightDataClient::with_data(v))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut data = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+            while let Some(byte) = seq.next_element()? {
+                data.push(byte);
+            }
+
+            Ok(EightDataClient::with_data(data))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for EightDataClient {
+        fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+            deserializer.deserialize_byte_buf(ClientVisitor)
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::{horizontal, EightDataClient};
+
+    const PIXELS: [u8; 6] = [
+        1, 1, 0,
+        1, 0, 1,
+    ];
+
+    #[test]
+    fn test_json() {
+        let image = horizontal(3, 2, &PIXELS);
+        let json = serde_json::to_string(&image).unwrap();
+        assert_eq!(r#"{"width":3,"height":2,"layout":"Horizontal","data":[192,160]}"#, json);
+
+        let image: HorizontalEightPxUintEight<EightDataClient> = serde_json::from_str(&json).unwrap();
+        assert_eq!(&[0b_1100_0000, 0b_1010_0000], image.as_vec());
+
+        let array: HorizontalEightPxUintEight<ArrayEightData<2>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(&[0b_1100_0000, 0b_1010_0000], array.as_vec());
+    }
+
+    #[test]
+    fn test_cbor() {
+        let image = horizontal(3, 2, &PIXELS);
+        let vertical = VerticalEightPxUintEight::from_image(&image);
+        let mut cbor = vec![];
+        ciborium::into_writer(&vertical, &mut cbor).unwrap();
+
+        let client: VerticalEightPxUintEight<EightDataClient> = ciborium::from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(vertical.as_vec(), client.as_vec());
+
+        let array: VerticalEightPxUintEight<ArrayEightData<3>> = ciborium::from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(vertical.as_vec(), array.as_vec());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let json = r#"{"width":3,"height":2,"layout":"Horizontal","data":[192,160]}"#;
+        assert!(serde_json::from_str::<VerticalEightPxUintEight<EightDataClient>>(json).is_err());
+        assert!(serde_json::from_str::<HorizontalEightPxUintEight<ArrayEightData<3>>>(json).is_err());
+
+        let short = r#"{"width":3,"height":3,"layout":"Horizontal","data":[192,160]}"#;
+        assert!(serde_json::from_str::<HorizontalEightPxUintEight<EightDataClient>>(short).is_err());
+
+        let huge = r#"{"width":18446744073709551615,"height":9,"layout":"Vertical","data":[]}"#;
+        assert!(serde_json::from_str::<VerticalEightPxUintEight<EightDataClient>>(huge).is_err());
+        let huge = r#"{"width":18446744073709551615,"height":9,"layout":"Horizontal","data":[]}"#;
+        assert!(serde_json::from_str::<HorizontalEightPxUintEight<EightDataClient>>(huge).is_err());
+    }
+
+    #[test]
+    fn test_geometry() {
+        let rectangle = Rectangle::new(1, 2, 3, 4);
+        let json = serde_json::to_string(&rectangle).unwrap();
+        assert_eq!(r#"{"x":1,"y":2,"width":3,"height":4}"#, json);
+        assert_eq!(rectangle, serde_json::from_str(&json).unwrap());
+
+        let part = Part::new(0, 1, 2, 3, 4, 5);
+        assert_eq!(part, serde_json::from_str(&serde_json::to_string(&part).unwrap()).unwrap());
+
+        assert_eq!("\"One\"", serde_json::to_string(&Mono::One).unwrap());
+        assert_eq!(Mono::Zero, serde_json::from_str("\"Zero\"").unwrap());
+    }
+}
@@ -0,0 +1,189 @@
+use crate::*;
+
+/// Pixels darker than the level become `Mono::One`. Inverted, brighter pixels do.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Threshold {
+    level: u8,
+    inverted: bool,
+}
+
+impl Default for Threshold {
+    fn default() -> Self {
+        Self::new(128)
+    }
+}
+
+impl Threshold {
+    pub fn new(level: u8) -> Self {
+        Self {
+            level,
+            inverted: false,
+        }
+    }
+
+    pub fn inverted(self) -> Self {
+        Self {
+            inverted: !self.inverted,
+            ..self
+        }
+    }
+
+    /// Compute the level that separates pixels into two classes best. (Otsu's method)
+    pub fn otsu(src: &[impl ActAsLuma]) -> Self {
+        let mut histogram = [0u64; 256];
+        for pixel in src {
+            histogram[pixel.luma() as usize] += 1;
+        }
+
+        let total = src.len() as u64;
+        let sum = histogram
+            .iter()
+            .enumerate()
+            .map(|(luma, n)| luma as u64 * n)
+            .sum::<u64>();
+
+        let mut best_level = 0;
+        let mut best_variance = 0.0;
+        let mut dark_count = 0;
+        let mut dark_sum = 0;
+
+        for (luma, n) in histogram.iter().enumerate() {
+            dark_count += n;
+            dark_sum += luma as u64 * n;
+
+            let bright_count = total - dark_count;
+            if dark_count == 0 || bright_count == 0 {
+                continue;
+            }
+
+            let dark_mean = dark_sum as f64 / dark_count as f64;
+            let bright_mean = (sum - dark_sum) as f64 / bright_count as f64;
+            let diff = dark_mean - bright_mean;
+            let variance = dark_count as f64 * bright_count as f64 * diff * diff;
+
+            if variance > best_variance {
+                best_variance = variance;
+                best_level = luma;
+            }
+        }
+
+        // pixels up to the best level are dark
+        Self::new((best_level + 1).min(255) as u8)
+    }
+
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    pub fn mono(&self, pixel: &impl ActAsLuma) -> Mono {
+        match (pixel.luma() < self.level) != self.inverted {
+            true => Mono::One,
+            false => Mono::Zero,
+        }
+    }
+}
+
+/// Dark colors are `Mono::One` as well as image conversions.
+impl ActAsMono for (u8, u8, u8) {
+    fn act_as(&self) -> Mono {
+        Threshold::default().mono(self)
+    }
+}
+
+impl ActAsMono for [u8; 3] {
+    fn act_as(&self) -> Mono {
+        Threshold::default().mono(self)
+    }
+}
+
+impl ActAsMono for (u8, u8, u8, u8) {
+    fn act_as(&self) -> Mono {
+        Threshold::default().mono(self)
+    }
+}
+
+impl ActAsMono for [u8; 4] {
+    fn act_as(&self) -> Mono {
+        Threshold::default().mono(self)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::EightDataClient;
+
+    #[test]
+    fn test_threshold() {
+        let threshold = Threshold::new(100);
+
+        assert_eq!(Mono::One, threshold.mono(&99u8));
+        assert_eq!(Mono::Zero, threshold.mono(&100u8));
+        assert_eq!(Mono::Zero, threshold.inverted().mono(&99u8));
+        assert_eq!(Mono::One, threshold.inverted().mono(&100u8));
+        assert_eq!(threshold, threshold.inverted().inverted());
+    }
+
+    #[test]
+    fn test_update_threshold() {
+        let mut image = HorizontalEightPxUintEight::new(4, 2, EightDataClient::new(2)).unwrap();
+        image.update_threshold((0, 0, 4, 2), &[
+            0u8, 50, 150, 255,
+            10, 200, 30, 255,
+        ], Threshold::new(100)).unwrap();
+
+        assert_eq!(
+            [
+                0b_1100_0000,
+                0b_1010_0000,
+            ],
+            image.as_vec()
+        );
+
+        image.update_threshold((0, 0, 4, 1), &[
+            [0, 0, 0],
+            [255, 255, 255],
+            [255, 0, 0],
+            [0, 255, 0],
+        ], Threshold::default().inverted()).unwrap();
+
+        assert_eq!([0b_0101_0000], image.as_vec()[0..1]);
+    }
+
+    #[test]
+    fn test_otsu() {
+        let src = [
+            10u8, 20, 30, 20, 10, 30,
+            200, 210, 220, 230, 200, 210,
+        ];
+        let threshold = Threshold::otsu(&src);
+
+        assert!(threshold.level() > 30 && threshold.level() <= 200, "{:?}", threshold);
+        assert_eq!(Mono::One, threshold.mono(&30u8));
+        assert_eq!(Mono::Zero, threshold.mono(&200u8));
+
+        assert_eq!(Threshold::new(1), Threshold::otsu(&[0u8; 4]));
+    }
+
+    #[test]
+    fn test_act_as_mono() {
+        let mut image = HorizontalEightPxUintEight::new(4, 1, EightDataClient::new(1)).unwrap();
+
+        image.update((0, 0, 4, 1), &[true, false, true, false]).unwrap();
+        assert_eq!([0b_1010_0000], image.as_vec());
+
+        image.update((0, 0, 4, 1), &[
+            (0, 0, 0, 255),
+            (0, 0, 0, 0),
+            (255, 255, 255, 255),
+            (0, 0, 255, 255),
+        ]).unwrap();
+        assert_eq!([0b_1001_0000], image.as_vec());
+    }
+}
@@ -0,0 +1,39 @@
+use crate::unix::EightDataClient;
+use crate::*;
+
+/// Horizontal image of `pixels` in row-major order.
+pub fn horizontal(
+    width: usize,
+    height: usize,
+    pixels: &[u8],
+) -> HorizontalEightPxUintEight<EightDataClient> {
+    let data = EightDataClient::new(
+        HorizontalEightPxUintEight::<EightDataClient>::data_length(width, height).unwrap(),
+    );
+    let mut image = HorizontalEightPxUintEight::new(width, height, data).unwrap();
+    image.update((0, 0, width, height), pixels).unwrap();
+    image
+}
+
+/// Vertical image of `pixels` in row-major order.
+pub fn vertical(
+    width: usize,
+    height: usize,
+    pixels: &[u8],
+) -> VerticalEightPxUintEight<EightDataClient> {
+    let data = EightDataClient::new(
+        VerticalEightPxUintEight::<EightDataClient>::data_length(width, height).unwrap(),
+    );
+    let mut image = VerticalEightPxUintEight::new(width, height, data).unwrap();
+    image.update((0, 0, width, height), pixels).unwrap();
+    image
+}
+
+/// 11 * 2 image over a byte boundary for round trips of file formats.
+#[rustfmt::skip]
+pub fn sample() -> HorizontalEightPxUintEight<EightDataClient> {
+    horizontal(11, 2, &[
+        1, 1, 0, 0, 0, 0, 1, 0,  0, 1, 0,
+        1, 0, 0, 0, 1, 0, 0, 1,  1, 0, 1,
+    ])
+}
@@ -0,0 +1,121 @@
+use crate::unix::EightDataClient;
+use crate::*;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+impl HorizontalEightPxUintEight<EightDataClient> {
+    /// Return a copy of the image in this layout.
+    pub fn from_image(src: &impl EightPxUintEight) -> Self {
+        let length = Self::data_length(src.width(), src.height())
+            .expect("a copy is not larger than the source");
+        let mut image = Self::new(src.width(), src.height(), EightDataClient::new(length))
+            .expect("length is computed from the size");
+        image.blit(0, 0, src, (0, 0, src.width(), src.height()));
+
+        image
+    }
+}
+
+impl VerticalEightPxUintEight<EightDataClient> {
+    /// Return a copy of the image in this layout.
+    pub fn from_image(src: &impl EightPxUintEight) -> Self {
+        let length = Self::data_length(src.width(), src.height())
+            .expect("a copy is not larger than the source");
+        let mut image = Self::new(src.width(), src.height(), EightDataClient::new(length))
+            .expect("length is computed from the size");
+        image.blit(0, 0, src, (0, 0, src.width(), src.height()));
+
+        image
+    }
+}
+
+fn blank(
+    width: usize,
+    height: usize,
+) -> EightPxUintEightResult<HorizontalEightPxUintEight<EightDataClient>> {
+    // sizes come from headers, so a size too large for memory is a broken header
+    let length = HorizontalEightPxUintEight::<EightDataClient>::data_length(width, height)
+        .map_err(|_| EightPxUintEightError::InvalidFormat)?;
+    HorizontalEightPxUintEight::new(width, height, EightDataClient::new(length))
+}
+
+/// Read PBM, XBM, BMP or PNG detected by its content. The threshold is used for PNG.
+pub fn load_image(
+    src: &[u8],
+    threshold: Threshold,
+) -> EightPxUintEightResult<HorizontalEightPxUintEight<EightDataClient>> {
+    if src.starts_with(PNG_SIGNATURE) {
+        return HorizontalEightPxUintEight::from_png(src, threshold);
+    }
+
+    match src.get(0..2) {
+        Some(b"P1") | Some(b"P4") => {
+            let pbm = Pbm::parse(src)?;
+            let mut image = blank(pbm.width(), pbm.height())?;
+            pbm.write_into(&mut image)?;
+            Ok(image)
+        }
+        Some(b"BM") => {
+            let bmp = Bmp::parse(src)?;
+            let mut image = blank(bmp.width(), bmp.height())?;
+            bmp.write_into(&mut image);
+            Ok(image)
+        }
+        _ => {
+            let text =
+                std::str::from_utf8(src).map_err(|_| EightPxUintEightError::InvalidFormat)?;
+            let xbm = Xbm::parse(text)?;
+            let mut image = blank(xbm.width(), xbm.height())?;
+            xbm.write_into(&mut image)?;
+            Ok(image)
+        }
+    }
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+
+    #[test]
+    fn test_load_image() {
+        let sources = vec![
+            to_pbm(&sample(), PbmKind::P1),
+            to_pbm(&sample(), PbmKind::P4),
+            to_xbm(&sample(), "image").into_bytes(),
+            to_bmp(&sample()),
+            to_png(&sample()).unwrap(),
+        ];
+
+        for src in sources {
+            let loaded = load_image(&src, Threshold::default()).unwrap();
+            assert_eq!(sample().as_vec(), loaded.as_vec());
+        }
+
+        assert!(load_image(b"unknown", Threshold::default()).is_err());
+    }
+
+    #[test]
+    fn test_crafted_size() {
+        assert_eq!(
+            Err(EightPxUintEightError::InvalidFormat),
+            load_image(b"P1 18446744073709551615 18446744073709551615 ", Threshold::default()).map(|_| ())
+        );
+        assert_eq!(
+            Err(EightPxUintEightError::InvalidLengthData),
+            load_image(b"P1 100000 100000 1", Threshold::default()).map(|_| ())
+        );
+        assert_eq!(
+            Err(EightPxUintEightError::InvalidFormat),
+            load_image(b"#define a_width 18446744073709551615\n#define a_height 18446744073709551615\nstatic char a_bits[] = {};", Threshold::default()).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_from_image() {
+        let vertical = VerticalEightPxUintEight::from_image(&sample());
+        assert_eq!((11, 2), (vertical.width(), vertical.height()));
+        assert_eq!(sample().as_vec(), HorizontalEightPxUintEight::from_image(&vertical).as_vec());
+    }
+}
@@ -1,3 +1,19 @@
+#[cfg(test)]
+mod fixture;
+mod load;
+mod png_io;
+mod recording_interface;
+mod source;
+mod svg;
+mod terminal_graphics;
 mod vec_eight_data;
 
+#[cfg(test)]
+pub use fixture::*;
+pub use load::*;
+pub use png_io::*;
+pub use recording_interface::*;
+pub use source::*;
+pub use svg::*;
+pub use terminal_graphics::*;
 pub use vec_eight_data::*;
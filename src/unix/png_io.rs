@@ -0,0 +1,203 @@
+use crate::unix::EightDataClient;
+use crate::*;
+use core::cmp::min;
+
+/// Encode the image into a 1-bit grayscale PNG. `Mono::One` is black.
+pub fn to_png(image: &impl EightPxUintEight) -> EightPxUintEightResult<Vec<u8>> {
+    let (width, height) = (image.width(), image.height());
+    let eight_length = compute_eight_length(width);
+
+    let mut rows = vec![0; eight_length * height];
+    for y in 0..height {
+        for x in 0..width {
+            if image.pixel(x, y) == Mono::Zero {
+                rows[eight_length * y + (x >> 3)] |= 0b_1000_0000 >> (x % 8);
+            }
+        }
+    }
+
+    let mut png = Vec::new();
+    {
+        let mut encoder = ::png::Encoder::new(&mut png, width as u32, height as u32);
+        encoder.set_color(::png::ColorType::Grayscale);
+        encoder.set_depth(::png::BitDepth::One);
+
+        let mut writer = encoder
+            .write_header()
+            .map_err(|_| EightPxUintEightError::InvalidFormat)?;
+        writer
+            .write_image_data(&rows)
+            .map_err(|_| EightPxUintEightError::InvalidFormat)?;
+    }
+
+    Ok(png)
+}
+
+/// Pixels of a decoded PNG.
+struct PngFrame {
+    buf: Vec<u8>,
+    info: ::png::OutputInfo,
+}
+
+impl PngFrame {
+    /// Decode any PNG.
+    fn decode(src: &[u8]) -> EightPxUintEightResult<Self> {
+        let mut decoder = ::png::Decoder::new(src);
+        decoder
+            .set_transformations(::png::Transformations::EXPAND | ::png::Transformations::STRIP_16);
+
+        let mut reader = decoder
+            .read_info()
+            .map_err(|_| EightPxUintEightError::InvalidFormat)?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut buf)
+            .map_err(|_| EightPxUintEightError::InvalidFormat)?;
+
+        Ok(Self { buf, info })
+    }
+
+    fn width(&self) -> usize {
+        self.info.width as usize
+    }
+
+    fn height(&self) -> usize {
+        self.info.height as usize
+    }
+
+    /// Draw pixels from (0, 0). Transparent pixels are composed on white.
+    fn write_into(
+        &self,
+        threshold: Threshold,
+        image: &mut impl EightPxUintEight,
+    ) -> EightPxUintEightResult<()> {
+        let channels = self.info.color_type.samples();
+        let width = min(self.width(), image.width());
+        let height = min(self.height(), image.height());
+
+        for y in 0..height {
+            for x in 0..width {
+                let i = self.info.line_size * y + channels * x;
+                let pixel = &self.buf[i..i + channels];
+
+                let rgba = match self.info.color_type {
+                    ::png::ColorType::Grayscale => (pixel[0], pixel[0], pixel[0], 255),
+                    ::png::ColorType::GrayscaleAlpha => (pixel[0], pixel[0], pixel[0], pixel[1]),
+                    ::png::ColorType::Rgb => (pixel[0], pixel[1], pixel[2], 255),
+                    ::png::ColorType::Rgba => (pixel[0], pixel[1], pixel[2], pixel[3]),
+                    // expanded into RGB
+                    ::png::ColorType::Indexed => return Err(EightPxUintEightError::InvalidFormat),
+                };
+
+                image.draw(x, y, threshold.mono(&rgba));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Decode any PNG into the image of any layout and data. Transparent pixels are composed on white.
+///
+/// Pixels are drawn from (0, 0), and pixels that overflow the image are discarded.
+pub fn decode_png_into(
+    src: &[u8],
+    threshold: Threshold,
+    image: &mut impl EightPxUintEight,
+) -> EightPxUintEightResult<()> {
+    PngFrame::decode(src)?.write_into(threshold, image)
+}
+
+impl HorizontalEightPxUintEight<EightDataClient> {
+    pub fn from_png(src: &[u8], threshold: Threshold) -> EightPxUintEightResult<Self> {
+        let frame = PngFrame::decode(src)?;
+        let (width, height) = (frame.width(), frame.height());
+        let data = EightDataClient::new(Self::data_length(width, height)?);
+        let mut image = Self::new(width, height, data)?;
+        frame.write_into(threshold, &mut image)?;
+
+        Ok(image)
+    }
+}
+
+impl VerticalEightPxUintEight<EightDataClient> {
+    pub fn from_png(src: &[u8], threshold: Threshold) -> EightPxUintEightResult<Self> {
+        let frame = PngFrame::decode(src)?;
+        let (width, height) = (frame.width(), frame.height());
+        let data = EightDataClient::new(Self::data_length(width, height)?);
+        let mut image = Self::new(width, height, data)?;
+        frame.write_into(threshold, &mut image)?;
+
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::*;
+
+    #[test]
+    fn test_round_trip() {
+        let png = to_png(&sample()).unwrap();
+
+        let horizontal = HorizontalEightPxUintEight::from_png(&png, Threshold::default()).unwrap();
+        assert_eq!(sample().as_vec(), horizontal.as_vec());
+
+        let vertical = VerticalEightPxUintEight::from_png(&png, Threshold::default()).unwrap();
+        assert_eq!((11, 2), (vertical.width(), vertical.height()));
+        assert_eq!([0b_0000_0011, 0b_0000_0001], vertical.as_vec()[0..2]);
+    }
+
+    #[test]
+    fn test_decode_rgba() {
+        let pixels: [u8; 16] = [
+            0, 0, 0, 255,
+            255, 255, 255, 255,
+            100, 100, 100, 255,
+            0, 0, 0, 0,
+        ];
+
+        let mut png = Vec::new();
+        {
+            let mut encoder = ::png::Encoder::new(&mut png, 4, 1);
+            encoder.set_color(::png::ColorType::Rgba);
+            encoder.set_depth(::png::BitDepth::Eight);
+            encoder.write_header().unwrap().write_image_data(&pixels).unwrap();
+        }
+
+        let image = HorizontalEightPxUintEight::from_png(&png, Threshold::default()).unwrap();
+        assert_eq!([0b_1010_0000], image.as_vec());
+
+        let image = HorizontalEightPxUintEight::from_png(&png, Threshold::new(64)).unwrap();
+        assert_eq!([0b_1000_0000], image.as_vec());
+    }
+
+    #[test]
+    fn test_decode_into() {
+        let png = to_png(&sample()).unwrap();
+
+        let mut array = VerticalEightPxUintEight::new(11, 2, ArrayEightData::new([0; 11])).unwrap();
+        decode_png_into(&png, Threshold::default(), &mut array).unwrap();
+        assert_eq!(VerticalEightPxUintEight::from_png(&png, Threshold::default()).unwrap().as_vec(), array.as_vec());
+
+        // overflowing pixels are discarded
+        let mut small = HorizontalEightPxUintEight::new(4, 1, EightDataClient::new(1)).unwrap();
+        decode_png_into(&png, Threshold::default(), &mut small).unwrap();
+        assert_eq!([0b_1100_0000], small.as_vec());
+
+        assert_eq!(
+            Err(EightPxUintEightError::InvalidFormat),
+            decode_png_into(b"not png", Threshold::default(), &mut small)
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            Err(EightPxUintEightError::InvalidFormat),
+            HorizontalEightPxUintEight::from_png(b"not png", Threshold::default()).map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::DisplayInterface;
+use core::convert::Infallible;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Transfer {
+    Command(u8),
+    Data(Vec<u8>),
+    WaitBusy,
+    Reset,
+}
+
+/// Interface that records transfers instead of sending them. For tests of drivers.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct RecordingInterface {
+    pub transfers: Vec<Transfer>,
+}
+
+impl RecordingInterface {
+    /// All commands in order.
+    pub fn commands(&self) -> Vec<u8> {
+        self.transfers
+            .iter()
+            .filter_map(|transfer| match transfer {
+                Transfer::Command(command) => Some(*command),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// All data in order joined.
+    pub fn data(&self) -> Vec<u8> {
+        self.transfers
+            .iter()
+            .filter_map(|transfer| match transfer {
+                Transfer::Data(data) => Some(data.as_slice()),
+                _ => None,
+            })
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.transfers.clear();
+    }
+}
+
+impl DisplayInterface for RecordingInterface {
+    type Error = Infallible;
+
+    fn send_command(&mut self, command: u8) -> Result<(), Self::Error> {
+        self.transfers.push(Transfer::Command(command));
+        Ok(())
+    }
+
+    fn send_data(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.transfers.push(Transfer::Data(data.to_vec()));
+        Ok(())
+    }
+
+    fn wait_busy(&mut self) -> Result<(), Self::Error> {
+        self.transfers.push(Transfer::WaitBusy);
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), Self::Error> {
+        self.transfers.push(Transfer::Reset);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_recording() {
+        let mut interface = RecordingInterface::default();
+        interface.reset().unwrap();
+        interface.send_command_with_data(0x44, &[0, 1]).unwrap();
+        interface.send_command(0x24).unwrap();
+        interface.send_data(&[2]).unwrap();
+        interface.wait_busy().unwrap();
+
+        assert_eq!(vec![0x44, 0x24], interface.commands());
+        assert_eq!(vec![0, 1, 2], interface.data());
+        assert_eq!(Transfer::Reset, interface.transfers[0]);
+        assert_eq!(Transfer::WaitBusy, interface.transfers[5]);
+
+        interface.clear();
+        assert!(interface.transfers.is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::*;
+
+/// Language of generated source.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum SourceLanguage {
+    Rust,
+    C,
+}
+
+/// Upper snake case identifier. ex: `logo-small.png` -> `LOGO_SMALL_PNG`
+fn constant_name(name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_uppercase(),
+            false => '_',
+        })
+        .collect::<String>();
+
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => name,
+        _ => format!("_{}", name),
+    }
+}
+
+fn layout_comment(image: &impl EightPxUintEight) -> String {
+    let layout = match image.layout() {
+        Layout::Horizontal => "HorizontalEightPxUintEight (lower x populates higher bit)",
+        Layout::Vertical => "VerticalEightPxUintEight (lower y populates lower bit)",
+    };
+
+    format!("{}, {} x {}", layout, image.width(), image.height())
+}
+
+fn byte_lines(bytes: &[u8]) -> String {
+    bytes
+        .chunks(12)
+        .map(|line| {
+            let line = line
+                .iter()
+                .map(|byte| format!("0x{:02x},", byte))
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!("    {}\n", line)
+        })
+        .collect()
+}
+
+/// Generate source that has width, height and bytes of the image as constants.
+pub fn to_source(image: &impl EightPxUintEight, name: &str, language: SourceLanguage) -> String {
+    let name = constant_name(name);
+    let bytes = image.as_vec();
+
+    match language {
+        SourceLanguage::Rust => format!(
+            "// {comment}\n\
+             pub const {name}_WIDTH: usize = {width};\n\
+             pub const {name}_HEIGHT: usize = {height};\n\
+             pub const {name}: [u8; {length}] = [\n\
+             {bytes}];\n",
+            comment = layout_comment(image),
+            name = name,
+            width = image.width(),
+            height = image.height(),
+            length = bytes.len(),
+            bytes = byte_lines(bytes),
+        ),
+        SourceLanguage::C => format!(
+            "// {comment}\n\
+             #ifndef {name}_H\n\
+             #define {name}_H\n\
+             \n\
+             #include <stdint.h>\n\
+             \n\
+             #define {name}_WIDTH {width}\n\
+             #define {name}_HEIGHT {height}\n\
+             \n\
+             static const uint8_t {name}[{length}] = {{\n\
+             {bytes}}};\n\
+             \n\
+             #endif\n",
+            comment = layout_comment(image),
+            name = name,
+            width = image.width(),
+            height = image.height(),
+            length = bytes.len(),
+            bytes = byte_lines(bytes),
+        ),
+    }
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod test {
+    use super::*;
+    use crate::unix::*;
+
+    const PIXELS: [u8; 6] = [
+        1, 1, 0,
+        1, 0, 1,
+    ];
+
+    #[test]
+    fn test_constant_name() {
+        assert_eq!("LOGO_SMALL_PNG", constant_name("logo-small.png"));
+        assert_eq!("_8PX", constant_name("8px"));
+    }
+
+    #[test]
+    fn test_rust() {
+        let image = horizontal(3, 2, &PIXELS);
+        assert_eq!(
+            "// HorizontalEightPxUintEight (lower x populates higher bit), 3 x 2
+pub const LOGO_WIDTH: usize = 3;
+pub const LOGO_HEIGHT: usize = 2;
+pub const LOGO: [u8; 2] = [
+    0xc0, 0xa0,
+];
+",
+            to_source(&image, "logo", SourceLanguage::Rust)
+        );
+    }
+
+    #[test]
+    fn test_c() {
+        let image = horizontal(3, 2, &PIXELS);
+        let vertical = VerticalEightPxUintEight::from_image(&image);
+
+        assert_eq!(
+            "// VerticalEightPxUintEight (lower y populates lower bit), 3 x 2
+#ifndef LOGO_H
+#define LOGO_H
+
+#include <stdint.h>
+
+#define LOGO_WIDTH 3
+#define LOGO_HEIGHT 2
+
+static const uint8_t LOGO[3] = {
+    0x03, 0x01, 0x02,
+};
+
+#endif
+",
+            to_source(&vertical, "logo", SourceLanguage::C)
+        );
+    }
+}
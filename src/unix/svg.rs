@@ -0,0 +1,136 @@
+use crate::*;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct SvgOptions {
+    /// Size of a pixel in the SVG.
+    pub pixel_size: usize,
+    /// Color of `Mono::One`.
+    pub foreground: String,
+    /// Color of `Mono::Zero`. Transparent with `None`.
+    pub background: Option<String>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            pixel_size: 1,
+            foreground: "#000000".to_string(),
+            background: Some("#ffffff".to_string()),
+        }
+    }
+}
+
+/// Escape a value of an attribute in double quotes.
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('"', "&quot;")
+}
+
+/// Encode the image into SVG. Runs of `Mono::One` in a row are merged into a rectangle.
+pub fn to_svg(image: &impl EightPxUintEight, options: &SvgOptions) -> String {
+    let (width, height) = (image.width(), image.height());
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+        width * options.pixel_size,
+        height * options.pixel_size,
+        width,
+        height,
+    );
+
+    if let Some(background) = &options.background {
+        svg.push_str(&format!(
+            "<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+            width,
+            height,
+            escape_attribute(background)
+        ));
+    }
+
+    svg.push_str(&format!(
+        "<g fill=\"{}\">\n",
+        escape_attribute(&options.foreground)
+    ));
+
+    for y in 0..height {
+        let mut x = 0;
+
+        while x < width {
+            let run = (x..width)
+                .take_while(|x| image.pixel(*x, y) == Mono::One)
+                .count();
+
+            if run > 0 {
+                svg.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\"/>\n",
+                    x, y, run
+                ));
+            }
+
+            x += run.max(1);
+        }
+    }
+
+    svg.push_str("</g>\n</svg>\n");
+
+    svg
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod test {
+    use crate::unix::*;
+
+    const PIXELS: [u8; 8] = [
+        1, 1, 0, 1,
+        0, 0, 0, 0,
+    ];
+
+    #[test]
+    fn test_svg() {
+        let image = horizontal(4, 2, &PIXELS);
+        assert_eq!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"4\" height=\"2\" viewBox=\"0 0 4 2\" shape-rendering=\"crispEdges\">
+<rect width=\"4\" height=\"2\" fill=\"#ffffff\"/>
+<g fill=\"#000000\">
+<rect x=\"0\" y=\"0\" width=\"2\" height=\"1\"/>
+<rect x=\"3\" y=\"0\" width=\"1\" height=\"1\"/>
+</g>
+</svg>
+",
+            to_svg(&image, &SvgOptions::default())
+        );
+    }
+
+    #[test]
+    fn test_options() {
+        let image = horizontal(4, 2, &PIXELS);
+        let options = SvgOptions {
+            pixel_size: 10,
+            foreground: "white".to_string(),
+            background: None,
+        };
+        let svg = to_svg(&image, &options);
+
+        assert!(svg.contains("width=\"40\" height=\"20\" viewBox=\"0 0 4 2\""));
+        assert!(svg.contains("<g fill=\"white\">"));
+        assert!(!svg.contains("<rect width="));
+    }
+
+    #[test]
+    fn test_escape() {
+        let image = horizontal(4, 2, &PIXELS);
+        let options = SvgOptions {
+            pixel_size: 1,
+            foreground: "red\"/><script>&".to_string(),
+            background: Some("\"".to_string()),
+        };
+        let svg = to_svg(&image, &options);
+
+        assert!(svg.contains("<g fill=\"red&quot;/>&lt;script>&amp;\">"));
+        assert!(svg.contains("fill=\"&quot;\"/>"));
+        assert!(!svg.contains("<script>"));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::unix::*;
+use crate::*;
+
+const KITTY_CHUNK_LENGTH: usize = 4096;
+
+/// Enlarge the image by nearest neighbor.
+fn scaled(
+    image: &impl EightPxUintEight,
+    scale: usize,
+) -> EightPxUintEightResult<HorizontalEightPxUintEight<EightDataClient>> {
+    let scale = scale.max(1);
+    let (width, height) = match (
+        image.width().checked_mul(scale),
+        image.height().checked_mul(scale),
+    ) {
+        (Some(width), Some(height)) => (width, height),
+        _ => return Err(EightPxUintEightError::InvalidLengthData),
+    };
+    let length = HorizontalEightPxUintEight::<EightDataClient>::data_length(width, height)?;
+    let mut result = HorizontalEightPxUintEight::new(width, height, EightDataClient::new(length))?;
+
+    for y in 0..height {
+        for x in 0..width {
+            result.draw(x, y, image.pixel(x / scale, y / scale));
+        }
+    }
+
+    Ok(result)
+}
+
+fn base64(src: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = String::with_capacity(src.len().div_ceil(3) * 4);
+
+    for chunk in src.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => result.push(TABLE[(n >> (18 - 6 * i) & 0b_0011_1111) as usize] as char),
+                false => result.push('='),
+            }
+        }
+    }
+
+    result
+}
+
+fn push_sixels(sixel: &mut String, sixels: &[u8]) {
+    let mut i = 0;
+
+    while i < sixels.len() {
+        let run = sixels[i..].iter().take_while(|c| **c == sixels[i]).count();
+
+        match run {
+            1..=3 => (0..run).for_each(|_| sixel.push(sixels[i] as char)),
+            _ => sixel.push_str(&format!("!{}{}", run, sixels[i] as char)),
+        }
+
+        i += run;
+    }
+}
+
+/// Encode the image into a Sixel sequence. `Mono::One` is black on white.
+pub fn to_sixel(image: &impl EightPxUintEight, scale: usize) -> EightPxUintEightResult<String> {
+    let image = scaled(image, scale)?;
+    let (width, height) = (image.width(), image.height());
+
+    let mut sixel = format!(
+        "\x1bP0;1;0q\"1;1;{};{}#0;2;100;100;100#1;2;0;0;0",
+        width, height
+    );
+    let mut sixels = vec![0; width];
+
+    for band_y in (0..height).step_by(6) {
+        for (register, color) in [(0, Mono::Zero), (1, Mono::One)] {
+            for (x, c) in sixels.iter_mut().enumerate() {
+                *c = (0..6)
+                    .filter(|dy| band_y + dy < height && image.pixel(x, band_y + dy) == color)
+                    .fold(0, |bits, dy| bits | 1 << dy)
+                    + 63;
+            }
+
+            sixel.push_str(&format!("#{}", register));
+            push_sixels(&mut sixel, &sixels);
+            sixel.push('$');
+        }
+        sixel.push('-');
+    }
+
+    sixel.push_str("\x1b\\");
+
+    Ok(sixel)
+}
+
+/// Encode the image into a kitty graphics protocol sequence that has PNG.
+pub fn to_kitty(image: &impl EightPxUintEight, scale: usize) -> EightPxUintEightResult<String> {
+    let image = scaled(image, scale)?;
+    let payload = base64(&to_png(&image)?);
+
+    let chunks = payload
+        .as_bytes()
+        .chunks(KITTY_CHUNK_LENGTH)
+        .collect::<Vec<_>>();
+    let mut kitty = String::with_capacity(payload.len() + chunks.len() * 16);
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = (i + 1 < chunks.len()) as u8;
+
+        match i {
+            0 => kitty.push_str(&format!("\x1b_Ga=T,f=100,m={};", more)),
+            _ => kitty.push_str(&format!("\x1b_Gm={};", more)),
+        }
+        // base64 is ASCII
+        kitty.push_str(core::str::from_utf8(chunk).unwrap_or_default());
+        kitty.push_str("\x1b\\");
+    }
+
+    Ok(kitty)
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod test {
+    use super::*;
+
+    const PIXELS: [u8; 4] = [
+        1, 0,
+        0, 1,
+    ];
+
+    #[test]
+    fn test_base64() {
+        assert_eq!("", base64(b""));
+        assert_eq!("Zg==", base64(b"f"));
+        assert_eq!("Zm8=", base64(b"fo"));
+        assert_eq!("Zm9v", base64(b"foo"));
+        assert_eq!("Zm9vYmFy", base64(b"foobar"));
+    }
+
+    #[test]
+    fn test_sixel() {
+        let image = horizontal(2, 2, &PIXELS);
+        assert_eq!(
+            "\x1bP0;1;0q\"1;1;2;2#0;2;100;100;100#1;2;0;0;0#0A@$#1@A$-\x1b\\",
+            to_sixel(&image, 1).unwrap()
+        );
+
+        // 6 * 6 pixels by scale 3
+        assert_eq!(
+            "\x1bP0;1;0q\"1;1;6;6#0;2;100;100;100#1;2;0;0;0#0wwwFFF$#1FFFwww$-\x1b\\",
+            to_sixel(&image, 3).unwrap()
+        );
+
+        let mut wide = HorizontalEightPxUintEight::new(8, 1, EightDataClient::new(1)).unwrap();
+        wide.fill((0, 0, 8, 1), Mono::One);
+        assert!(to_sixel(&wide, 1).unwrap().contains("#0!8?$#1!8@$-"));
+    }
+
+    #[test]
+    fn test_kitty() {
+        let image = horizontal(2, 2, &PIXELS);
+        let payload = base64(&to_png(&scaled(&image, 2).unwrap()).unwrap());
+        assert_eq!(
+            format!("\x1b_Ga=T,f=100,m=0;{}\x1b\\", payload),
+            to_kitty(&image, 2).unwrap()
+        );
+
+        // noise is not compressed into a chunk
+        let mut noise = HorizontalEightPxUintEight::new(256, 256, EightDataClient::new(8192)).unwrap();
+        let mut n: u32 = 1;
+        for y in 0..256 {
+            for x in 0..256 {
+                n ^= n << 13;
+                n ^= n >> 17;
+                n ^= n << 5;
+                noise.draw(x, y, (n & 1 == 1).act_as());
+            }
+        }
+
+        let kitty = to_kitty(&noise, 1).unwrap();
+        assert!(kitty.starts_with("\x1b_Ga=T,f=100,m=1;"));
+        assert!(kitty.contains("\x1b\\\x1b_Gm=0;"));
+        assert!(kitty.ends_with("\x1b\\"));
+    }
+}
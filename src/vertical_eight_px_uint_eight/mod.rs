@@ -13,7 +13,7 @@ impl<D: EightData> VerticalEightPxUintEight<D> {
     pub fn new(width: usize, height: usize, eight_data: D) -> EightPxUintEightResult<Self> {
         let eight_length = compute_eight_length(height);
 
-        if width * eight_length != eight_data.len() {
+        if Self::data_length(width, height)? != eight_data.len() {
             return Err(EightPxUintEightError::InvalidLengthData);
         }
 
@@ -24,11 +24,72 @@ impl<D: EightData> VerticalEightPxUintEight<D> {
             eight_data,
         })
     }
+
+    /// Same as `new` but the length of data is not checked, so it can make a constant.
+    ///
+    /// Only for `include_bitmap!`. A wrong length panics when the image is read.
+    #[doc(hidden)]
+    pub const fn from_parts(width: usize, height: usize, eight_data: D) -> Self {
+        Self {
+            width,
+            height,
+            eight_length: compute_eight_length(height),
+            eight_data,
+        }
+    }
+
+    /// Length of data of the size. A size whose length overflows is `InvalidLengthData`.
+    pub fn data_length(width: usize, height: usize) -> EightPxUintEightResult<usize> {
+        width
+            .checked_mul(compute_eight_length(height))
+            .ok_or(EightPxUintEightError::InvalidLengthData)
+    }
+
+    /// Length of data for `crop` of the rectangle.
+    pub fn crop_length(&self, xywh: impl ActAsXywh) -> usize {
+        let rectangle = clip(xywh, self.width, self.height);
+        Self::data_length(rectangle.width, rectangle.height)
+            .expect("the rectangle is clipped to this image")
+    }
+
+    /// Return a new image of the rectangle. The rectangle is clipped to this image,
+    /// and the size of data is `crop_length`. A rectangle outside of this image is 0 * 0.
+    pub fn crop<E: EightData>(
+        &self,
+        xywh: impl ActAsXywh,
+        eight_data: E,
+    ) -> EightPxUintEightResult<VerticalEightPxUintEight<E>> {
+        let rectangle = clip(xywh, self.width, self.height);
+        let mut image =
+            VerticalEightPxUintEight::new(rectangle.width, rectangle.height, eight_data)?;
+        image.blit(0, 0, self, rectangle);
+
+        Ok(image)
+    }
+
+    /// Return a new image of width * height that has this image at the anchor.
+    pub fn resize<E: EightData>(
+        &self,
+        width: usize,
+        height: usize,
+        anchor: Anchor,
+        fill: Mono,
+        eight_data: E,
+    ) -> EightPxUintEightResult<VerticalEightPxUintEight<E>> {
+        let mut image = VerticalEightPxUintEight::new(width, height, eight_data)?;
+        resize_into(self, &mut image, anchor, fill);
+
+        Ok(image)
+    }
 }
 
 impl<D: EightData> EightPxUintEight for VerticalEightPxUintEight<D> {
     type EightData = D;
 
+    fn layout(&self) -> Layout {
+        Layout::Vertical
+    }
+
     fn width(&self) -> usize {
         self.width
     }
@@ -83,6 +144,16 @@ impl<D: EightData> EightPxUintEight for VerticalEightPxUintEight<D> {
         }
     }
 
+    fn pixel(&self, x: usize, y: usize) -> Mono {
+        let data = self.eight_data.core();
+        let data_i = self.width * (y >> 3) + x;
+
+        match data[data_i] & (0b_0000_0001 << (y % 8)) {
+            0 => Mono::Zero,
+            _ => Mono::One,
+        }
+    }
+
     fn compute_part(&self, xywh: impl ActAsXywh) -> Part {
         let (x, y, width, height) = xywh.xywh();
 
@@ -112,6 +183,7 @@ impl<D: EightData> EightPxUintEight for VerticalEightPxUintEight<D> {
 #[cfg(test)]
 #[cfg(feature = "std")]
 #[rustfmt::skip]
+#[allow(clippy::useless_vec)]
 mod test {
     use crate::*;
     use crate::unix::EightDataClient;
@@ -152,6 +224,13 @@ mod test {
         assert!(image.is_err());
     }
 
+    #[test]
+    fn test_data_length() {
+        assert_eq!(Ok(6), VerticalEightPxUintEight::<EightDataClient>::data_length(3, 9));
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), VerticalEightPxUintEight::<EightDataClient>::data_length(usize::MAX, usize::MAX));
+        assert_eq!(Err(EightPxUintEightError::InvalidLengthData), VerticalEightPxUintEight::new(usize::MAX, usize::MAX, EightDataClient::new(0)).map(|_| ()));
+    }
+
     #[test]
     fn test_short() {
         let data = EightDataClient::new(5);
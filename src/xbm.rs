@@ -0,0 +1,217 @@
+use crate::*;
+use core::cmp::min;
+
+/// X BitMap. Lower x populates lower bit. `7654_3210`
+///
+/// A set bit of XBM is the foreground, and it is treated as `Mono::One`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Xbm<'a> {
+    width: usize,
+    height: usize,
+    body: &'a str,
+}
+
+impl<'a> Xbm<'a> {
+    pub fn parse(src: &'a str) -> EightPxUintEightResult<Self> {
+        let mut width = None;
+        let mut height = None;
+
+        for line in src.lines() {
+            let mut words = line.split_whitespace();
+
+            if words.next() != Some("#define") {
+                continue;
+            }
+
+            match (words.next(), words.next().map(|n| n.parse::<usize>())) {
+                (Some(name), Some(Ok(n))) if name.ends_with("width") => width = Some(n),
+                (Some(name), Some(Ok(n))) if name.ends_with("height") => height = Some(n),
+                _ => (),
+            }
+        }
+
+        let (width, height) = match (width, height) {
+            (Some(width), Some(height)) => (width, height),
+            _ => return Err(EightPxUintEightError::InvalidFormat),
+        };
+
+        let body = match (src.find('{'), src.rfind('}')) {
+            (Some(start), Some(end)) if start < end => &src[start + 1..end],
+            _ => return Err(EightPxUintEightError::InvalidFormat),
+        };
+
+        let xbm = Self {
+            width,
+            height,
+            body,
+        };
+
+        let length = HorizontalEightPxUintEight::<&mut [u8]>::data_length(width, height)
+            .map_err(|_| EightPxUintEightError::InvalidFormat)?;
+
+        let mut count = 0;
+        for byte in xbm.bytes() {
+            byte?;
+            count += 1;
+        }
+
+        if count != length {
+            return Err(EightPxUintEightError::InvalidLengthData);
+        }
+
+        Ok(xbm)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Return bytes as written in XBM. Bit order is not reversed.
+    pub fn bytes(&self) -> impl Iterator<Item = EightPxUintEightResult<u8>> + 'a {
+        self.body
+            .split(',')
+            .map(str::trim)
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let parsed = match word.strip_prefix("0x").or_else(|| word.strip_prefix("0X")) {
+                    Some(hex) => u8::from_str_radix(hex, 16),
+                    None => word.parse::<u8>(),
+                };
+                parsed.map_err(|_| EightPxUintEightError::InvalidFormat)
+            })
+    }
+
+    /// Draw pixels from (0, 0). Pixels that overflow are discarded.
+    pub fn write_into(&self, image: &mut impl EightPxUintEight) -> EightPxUintEightResult<()> {
+        let eight_length = compute_eight_length(self.width);
+        let width = min(self.width, image.width());
+        let height = min(self.height, image.height());
+
+        for (i, byte) in self.bytes().enumerate() {
+            let byte = byte?;
+            let y = i / eight_length;
+            let eight_x = i % eight_length;
+
+            if y >= height {
+                break;
+            }
+
+            for step_x in 0..8 {
+                let x = eight_x * 8 + step_x;
+
+                if x >= width {
+                    break;
+                }
+
+                let color = match byte & (0b_0000_0001 << step_x) {
+                    0 => Mono::Zero,
+                    _ => Mono::One,
+                };
+
+                image.draw(x, y, color);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Encode the image into XBM. `name` prefixes `_width`, `_height` and `_bits`.
+#[cfg(feature = "std")]
+pub fn to_xbm(image: &impl EightPxUintEight, name: &str) -> String {
+    let (width, height) = (image.width(), image.height());
+    let mut bytes = Vec::with_capacity(compute_eight_length(width) * height);
+
+    for y in 0..height {
+        for eight_x in 0..compute_eight_length(width) {
+            let mut byte = 0;
+
+            for step_x in 0..min(8, width - eight_x * 8) {
+                if image.pixel(eight_x * 8 + step_x, y) == Mono::One {
+                    byte |= 0b_0000_0001 << step_x;
+                }
+            }
+
+            bytes.push(format!("0x{:02x}", byte));
+        }
+    }
+
+    let lines = bytes
+        .chunks(12)
+        .map(|line| format!("   {}", line.join(", ")))
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    format!(
+        "#define {name}_width {}\n#define {name}_height {}\nstatic unsigned char {name}_bits[] = {{\n{} }};\n",
+        width,
+        height,
+        lines,
+        name = name,
+    )
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+#[rustfmt::skip]
+mod test {
+    use crate::*;
+    use crate::unix::EightDataClient;
+
+    const SRC: &str = "#define icon_width 11
+#define icon_height 3
+static unsigned char icon_bits[] = {
+   0x43, 0x02, 0x91, 0x05, 0x00, 0x04 };
+";
+
+    #[test]
+    fn test_parse() {
+        let xbm = Xbm::parse(SRC).unwrap();
+        assert_eq!((11, 3), (xbm.width(), xbm.height()));
+
+        let mut image = HorizontalEightPxUintEight::new(11, 3, EightDataClient::new(6)).unwrap();
+        xbm.write_into(&mut image).unwrap();
+
+        assert_eq!(
+            [
+                0b_1100_0010, 0b_0100_0000,
+                0b_1000_1001, 0b_1010_0000,
+                0b_0000_0000, 0b_0010_0000,
+            ],
+            image.as_vec()
+        );
+    }
+
+    #[test]
+    fn test_to_xbm() {
+        let xbm = Xbm::parse(SRC).unwrap();
+        let mut image = HorizontalEightPxUintEight::new(11, 3, EightDataClient::new(6)).unwrap();
+        xbm.write_into(&mut image).unwrap();
+
+        assert_eq!(SRC, to_xbm(&image, "icon"));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            Err(EightPxUintEightError::InvalidFormat),
+            Xbm::parse("#define icon_width 18446744073709551615\n#define icon_height 9\nstatic char icon_bits[] = { 0x00 };")
+        );
+        assert_eq!(
+            Err(EightPxUintEightError::InvalidFormat),
+            Xbm::parse("#define icon_width 8\nstatic char icon_bits[] = { 0x00 };")
+        );
+        assert_eq!(
+            Err(EightPxUintEightError::InvalidFormat),
+            Xbm::parse("#define icon_width 8\n#define icon_height 1\nstatic char icon_bits[] = { 0xfff };")
+        );
+        assert_eq!(
+            Err(EightPxUintEightError::InvalidLengthData),
+            Xbm::parse("#define icon_width 8\n#define icon_height 2\nstatic char icon_bits[] = { 0x00 };")
+        );
+    }
+}